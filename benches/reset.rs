@@ -0,0 +1,103 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use resettable::{Resettable, ResettableShared, ResettableWrapper};
+
+// Resets, the first mutation that stashes and the mutations after it, and
+// clones, for a small and a large value.
+fn bench<T: Clone>(c: &mut Criterion, name: &str, value: T, mutate: fn(&mut T)) {
+    let dirty = || {
+        let mut wrapper = ResettableWrapper::new(value.clone());
+        mutate(&mut wrapper);
+        wrapper
+    };
+
+    c.bench_function(&format!("reset clean {}", name), |b| {
+        b.iter_batched(
+            || ResettableWrapper::new(value.clone()),
+            |wrapper| black_box(wrapper.reset()),
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function(&format!("reset dirty {}", name), |b| {
+        b.iter_batched(
+            dirty,
+            |wrapper| black_box(wrapper.reset()),
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function(&format!("first mutation {}", name), |b| {
+        b.iter_batched(
+            || ResettableWrapper::new(value.clone()),
+            |mut wrapper| {
+                mutate(&mut wrapper);
+                wrapper
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function(&format!("next mutation {}", name), |b| {
+        let mut wrapper = dirty();
+        b.iter(|| mutate(black_box(&mut wrapper)))
+    });
+
+    c.bench_function(&format!("clone clean {}", name), |b| {
+        let wrapper = ResettableWrapper::new(value.clone());
+        b.iter(|| black_box(&wrapper).clone())
+    });
+
+    c.bench_function(&format!("clone dirty {}", name), |b| {
+        let wrapper = dirty();
+        b.iter(|| black_box(&wrapper).clone())
+    });
+}
+
+fn wrapper(c: &mut Criterion) {
+    bench(c, "u64", 0u64, |value| *value = value.wrapping_add(1));
+    bench(c, "vec", vec![0u8; 4096], |value| {
+        value[0] = value[0].wrapping_add(1)
+    });
+}
+
+fn preserving_capacity(c: &mut Criterion) {
+    let value = vec![0u8; 4096];
+
+    c.bench_function("reset dirty preserving capacity vec", |b| {
+        b.iter_batched(
+            || {
+                let mut wrapper = ResettableWrapper::new(value.clone());
+                wrapper[0] = wrapper[0].wrapping_add(1);
+                wrapper
+            },
+            |mut wrapper| {
+                wrapper.reset_preserving_capacity();
+                black_box(wrapper)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// Many dirty copies of one big value that are reset once.
+fn shared(c: &mut Criterion) {
+    let value = vec![0u8; 4096];
+
+    c.bench_function("copy dirty eager", |b| {
+        let mut wrapper = ResettableWrapper::new(value.clone());
+        wrapper.push(1);
+        b.iter(|| black_box(wrapper.clone().reset()))
+    });
+
+    c.bench_function("copy dirty shared", |b| {
+        let mut wrapper = ResettableShared::new(value.clone());
+        wrapper.push(1);
+        b.iter(|| black_box(wrapper.clone().reset()))
+    });
+}
+
+criterion_group!(benches, wrapper, preserving_capacity, shared);
+criterion_main!(benches);
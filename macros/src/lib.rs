@@ -2,3 +2,589 @@
 // SPDX-License-Identifier: MIT
 
 #![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, format_ident, ToTokens};
+use syn::{
+    spanned::Spanned, parse_macro_input, Attribute, Data, DeriveInput, Field, Fields,
+    GenericArgument, Generics, Ident, Lit, Member, Meta, NestedMeta, Path, PathArguments, Type,
+};
+
+/// Derives `Resettable` by resetting every field.
+///
+/// A field marked `#[resettable(skip)]` is moved into the result unchanged,
+/// so it doesn't need to implement `Resettable`. A field marked
+/// `#[resettable(with = "path")]` is reset by calling `path(field)`, where
+/// `path` is a `fn(T) -> T`.
+///
+/// Fields can be put in named groups with `#[resettable(group = "name")]`,
+/// repeated for several groups. Every group gets an inherent method
+/// `reset_group_name(self) -> Self` that resets only the fields of that
+/// group and moves the others unchanged.
+///
+/// With `#[resettable(report)]` on the type, an inherent method
+/// `dirty_fields(&self) -> Vec<&'static str>` lists the `ResettableWrapper`
+/// fields that are dirty, tuple fields by index. A field marked
+/// `#[resettable(flatten)]` holds a type with `report` itself, and
+/// `dirty_paths(&self) -> Vec<String>` lists its dirty fields as well, prefixed
+/// with the field name, e.g. `address.city`.
+#[proc_macro_derive(Resettable, attributes(resettable))]
+pub fn derive_resettable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ResettableMut` by resetting every field in place.
+///
+/// Fields marked `#[resettable(skip)]` are left as is. `with` is not
+/// supported, a `fn(T) -> T` can't be applied through a mutable reference.
+#[proc_macro_derive(ResettableMut, attributes(resettable))]
+pub fn derive_resettable_mut(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_mut(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Wraps every field of a struct in `ResettableWrapper` and implements
+/// `Resettable` for it.
+///
+/// `#[resettable] struct Form { name: String }` becomes a struct with a
+/// `name: ResettableWrapper<String>` field and a `name(&self) -> &String`
+/// accessor. Fields marked `#[resettable(skip)]` keep their type and are
+/// moved through `reset` unchanged, groups work as in the derive.
+///
+/// Type-level options of the derive are accepted as arguments, e.g.
+/// `#[resettable(report)]`, or as a separate `#[resettable(report)]` after
+/// `#[resettable]`. Fields marked `#[resettable(flatten)]` or
+/// `#[resettable(with = "..")]` are not wrapped, the latter are reset by the
+/// given function as in the derive.
+#[proc_macro_attribute]
+pub fn resettable(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(args);
+    let mut input = parse_macro_input!(item as DeriveInput);
+    if !args.is_empty() {
+        input.attrs.push(syn::parse_quote!(#[resettable(#args)]));
+    }
+    expand_attribute(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    if let Data::Union(data) = &input.data {
+        let message = "unions cannot derive `Resettable`";
+        return Err(syn::Error::new(data.union_token.span, message));
+    }
+
+    let body = reset_body(input, None)?;
+
+    let mut groups = Vec::new();
+    for field in all_fields(&input.data) {
+        for group in FieldAttrs::parse(&field.attrs)?.groups {
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+    }
+    let mut group_methods = groups
+        .iter()
+        .map(|group| {
+            let method = format_ident!("reset_group_{}", group);
+            let body = reset_body(input, Some(group))?;
+            Ok(quote! {
+                #vis fn #method(self) -> Self {
+                    #body
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let bound = syn::parse_quote!(::resettable::Resettable);
+    let generics = with_bounds(name, &input.generics, &input.data, &bound)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut expanded = quote! {
+        impl #impl_generics ::resettable::Resettable for #name #ty_generics #where_clause {
+            fn reset(self) -> Self {
+                #body
+            }
+        }
+    };
+    if report(&input.attrs)? {
+        group_methods.push(report_methods(input)?);
+    } else if let Some(field) = all_fields(&input.data)
+        .into_iter()
+        .find(|field| FieldAttrs::parse(&field.attrs).map_or(false, |attrs| attrs.flatten))
+    {
+        let message = "`flatten` requires `#[resettable(report)]` on the type";
+        return Err(syn::Error::new_spanned(field, message));
+    }
+    if !group_methods.is_empty() {
+        expanded.extend(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#group_methods)*
+            }
+        });
+    }
+    Ok(expanded)
+}
+
+fn expand_mut(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let arms = match &input.data {
+        Data::Struct(data) => vec![reset_mut_arm(&syn::parse_quote!(#name), &data.fields)?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                reset_mut_arm(&syn::parse_quote!(#name::#ident), &variant.fields)
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        Data::Union(data) => {
+            let message = "unions cannot derive `ResettableMut`";
+            return Err(syn::Error::new(data.union_token.span, message));
+        },
+    };
+
+    let bound = syn::parse_quote!(::resettable::ResettableMut);
+    let generics = with_bounds(name, &input.generics, &input.data, &bound)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::resettable::ResettableMut for #name #ty_generics #where_clause {
+            fn reset_mut(&mut self) {
+                match self { #(#arms)* }
+            }
+        }
+    })
+}
+
+fn expand_attribute(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &mut input.data {
+        Data::Struct(data) => &mut data.fields,
+        _ => {
+            let message = "`#[resettable]` can only be used on structs";
+            return Err(syn::Error::new(Span::call_site(), message));
+        },
+    };
+
+    let mut accessors = Vec::new();
+    for field in fields.iter_mut() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if attrs.skip || attrs.flatten || attrs.with.is_some() {
+            continue;
+        }
+        let ty = &field.ty;
+        if let Some(ident) = &field.ident {
+            let vis = &field.vis;
+            accessors.push(quote! {
+                #vis fn #ident(&self) -> &#ty {
+                    &self.#ident
+                }
+            });
+        }
+        field.ty = syn::parse_quote!(::resettable::ResettableWrapper<#ty>);
+    }
+
+    // The impl still needs the resettable attributes, the struct must not keep
+    // them. A type-level one would invoke this macro again.
+    let implementation = expand(&input)?;
+    input.attrs.retain(|attr| !attr.path.is_ident("resettable"));
+    if let Data::Struct(data) = &mut input.data {
+        for field in data.fields.iter_mut() {
+            field.attrs.retain(|attr| !attr.path.is_ident("resettable"));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let accessors = if accessors.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#accessors)*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #input
+        #implementation
+        #accessors
+    })
+}
+
+// Resets the fields of `group`, or every field if `group` is `None`.
+fn reset_body(input: &DeriveInput, group: Option<&Ident>) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    match &input.data {
+        Data::Struct(data) => {
+            let arm = reset_arm(&syn::parse_quote!(#name), &data.fields, group)?;
+            Ok(quote!(match self { #arm }))
+        },
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    reset_arm(&syn::parse_quote!(#name::#ident), &variant.fields, group)
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote!(match self { #(#arms)* }))
+        },
+        Data::Union(_) => unreachable!("rejected in `expand`"),
+    }
+}
+
+fn all_fields(data: &Data) -> Vec<&Field> {
+    match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => Vec::new(),
+    }
+}
+
+// Parses `#[resettable(report)]` on the type.
+fn report(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut report = false;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("resettable")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `resettable(...)`")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("report") => report = true,
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "unknown resettable attribute",
+                    ))
+                },
+            }
+        }
+    }
+    Ok(report)
+}
+
+// Generates `dirty_fields` and `dirty_paths`, looking at the fields whose
+// type is spelled `ResettableWrapper<..>`, possibly with a path, and at the
+// flattened ones.
+fn report_methods(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let arm = |path: TokenStream2, fields: &Fields| -> syn::Result<_> {
+        let mut members = Vec::new();
+        let mut bindings = Vec::new();
+        let mut labels = Vec::new();
+        let mut nested = Vec::new();
+        let mut nested_labels = Vec::new();
+        for (index, field) in fields.iter().enumerate() {
+            let wrapper = match &field.ty {
+                Type::Path(ty) => ty
+                    .path
+                    .segments
+                    .last()
+                    .map_or(false, |segment| segment.ident == "ResettableWrapper"),
+                _ => false,
+            };
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            if attrs.skip || !(wrapper || attrs.flatten) {
+                continue;
+            }
+            let binding = format_ident!("__self_{}", index);
+            let (member, label) = match &field.ident {
+                Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+                None => (Member::Unnamed(index.into()), index.to_string()),
+            };
+            if attrs.flatten {
+                nested.push(binding.clone());
+                nested_labels.push(label);
+            } else {
+                bindings.push(binding.clone());
+                labels.push(label);
+            }
+            members.push((member, binding));
+        }
+        let (members, all) = members.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+        Ok((
+            quote! {
+                #path { #(#members: #all,)* .. } => {
+                    #(
+                        if ::resettable::ResettableWrapper::is_dirty(#bindings) {
+                            fields.push(#labels);
+                        }
+                    )*
+                },
+            },
+            quote! {
+                #path { #(#members: #all,)* .. } => {
+                    #(
+                        if ::resettable::ResettableWrapper::is_dirty(#bindings) {
+                            paths.push(::resettable::__private::String::from(#labels));
+                        }
+                    )*
+                    #(
+                        for nested in #nested.dirty_paths() {
+                            let mut path = ::resettable::__private::String::from(#nested_labels);
+                            path.push('.');
+                            path.push_str(&nested);
+                            paths.push(path);
+                        }
+                    )*
+                },
+            },
+        ))
+    };
+    let arms = match &input.data {
+        Data::Struct(data) => vec![arm(quote!(#name), &data.fields)?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                arm(quote!(#name::#ident), &variant.fields)
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Union(_) => unreachable!("rejected in `expand`"),
+    };
+    let (field_arms, path_arms) = arms.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+
+    Ok(quote! {
+        #vis fn dirty_fields(&self) -> ::resettable::__private::Vec<&'static str> {
+            let mut fields = ::resettable::__private::Vec::new();
+            match self { #(#field_arms)* }
+            fields
+        }
+
+        #vis fn dirty_paths(
+            &self,
+        ) -> ::resettable::__private::Vec<::resettable::__private::String> {
+            let mut paths = ::resettable::__private::Vec::new();
+            match self { #(#path_arms)* }
+            paths
+        }
+    })
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    flatten: bool,
+    with: Option<Path>,
+    groups: Vec<Ident>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut this = FieldAttrs::default();
+        let mut skip_attr = None;
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("resettable")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected `resettable(...)`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                        this.skip = true;
+                        skip_attr = Some(attr);
+                    },
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => {
+                        this.flatten = true
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("with") => {
+                        match &nv.lit {
+                            Lit::Str(lit) => this.with = Some(lit.parse()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                        }
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("group") => {
+                        match &nv.lit {
+                            Lit::Str(lit) => this.groups.push(lit.parse()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                        }
+                    },
+                    nested => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "unknown resettable attribute",
+                        ))
+                    },
+                }
+            }
+        }
+        if let Some(attr) = skip_attr {
+            if this.flatten || this.with.is_some() || !this.groups.is_empty() {
+                let message = "`skip` cannot be combined with other resettable attributes";
+                return Err(syn::Error::new_spanned(attr, message));
+            }
+        }
+        Ok(this)
+    }
+
+    fn custom(&self) -> bool {
+        self.skip || self.with.is_some()
+    }
+}
+
+// Requires `Field: Bound` for every field whose type mentions one of the type
+// parameters. Bounds on concrete types are left out, they are checked at the
+// call site anyway and would overflow for recursive types. A field type that
+// refers to the type itself, e.g. `Vec<Node<T>>` or `Box<Swap<B, A>>`, can't be
+// bounded either, the bound would depend on the impl being generated. It
+// requires `P: Bound` for each parameter it mentions instead, which is what the
+// impl for the nested type needs.
+fn with_bounds(
+    name: &Ident,
+    generics: &Generics,
+    data: &Data,
+    bound: &Path,
+) -> syn::Result<Generics> {
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let mut generics = generics.clone();
+    let mut bounded = Vec::new();
+    let mut bound_on = |generics: &mut Generics, ty: TokenStream2| {
+        if !bounded.contains(&ty.to_string()) {
+            bounded.push(ty.to_string());
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#ty: #bound));
+        }
+    };
+    for field in all_fields(data) {
+        if FieldAttrs::parse(&field.attrs)?.custom() {
+            continue;
+        }
+        let ty = &field.ty;
+        let tokens = ty.to_token_stream();
+        if refers_to(ty, name) {
+            for param in &params {
+                if mentions(tokens.clone(), core::slice::from_ref(param)) {
+                    bound_on(&mut generics, param.to_token_stream());
+                }
+            }
+        } else if mentions(tokens.clone(), &params) {
+            bound_on(&mut generics, tokens);
+        }
+    }
+    Ok(generics)
+}
+
+// Whether `ty` contains the path `name` or `Self`, with any generic arguments.
+// Longer paths like `other::Node<T>` are different types.
+fn refers_to(ty: &Type, name: &Ident) -> bool {
+    match ty {
+        Type::Path(ty) => {
+            let segments = &ty.path.segments;
+            let own = ty.qself.is_none()
+                && ty.path.leading_colon.is_none()
+                && segments.len() == 1
+                && (segments[0].ident == *name || segments[0].ident == "Self");
+            own || segments.iter().any(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.iter().any(|argument| match argument {
+                        GenericArgument::Type(ty) => refers_to(ty, name),
+                        _ => false,
+                    })
+                },
+                _ => false,
+            })
+        },
+        Type::Array(ty) => refers_to(&ty.elem, name),
+        Type::Slice(ty) => refers_to(&ty.elem, name),
+        Type::Ptr(ty) => refers_to(&ty.elem, name),
+        Type::Reference(ty) => refers_to(&ty.elem, name),
+        Type::Paren(ty) => refers_to(&ty.elem, name),
+        Type::Group(ty) => refers_to(&ty.elem, name),
+        Type::Tuple(ty) => ty.elems.iter().any(|ty| refers_to(ty, name)),
+        _ => false,
+    }
+}
+
+fn mentions(tokens: TokenStream2, params: &[Ident]) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ident) => params.contains(&ident),
+        TokenTree::Group(group) => mentions(group.stream(), params),
+        _ => false,
+    })
+}
+
+// Generates `Path { a: __self_0, .. } => Path { a: reset(__self_0), .. }`,
+// the braced form works for named, tuple and unit fields alike.
+fn reset_arm(path: &Path, fields: &Fields, group: Option<&Ident>) -> syn::Result<TokenStream2> {
+    let mut members = Vec::new();
+    let mut bindings = Vec::new();
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let binding = format_ident!("__self_{}", index);
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let excluded = group.map_or(false, |group| !attrs.groups.contains(group));
+        let value = if attrs.skip || excluded {
+            quote!(#binding)
+        } else if let Some(with) = &attrs.with {
+            quote!(#with(#binding))
+        } else {
+            // Spanned so that a missing impl is reported at the field.
+            let ty = &field.ty;
+            quote_spanned!(ty.span()=> <#ty as ::resettable::Resettable>::reset(#binding))
+        };
+        members.push(member);
+        bindings.push(binding);
+        values.push(value);
+    }
+
+    Ok(quote! {
+        #path { #(#members: #bindings,)* } => #path { #(#members: #values,)* },
+    })
+}
+
+// Generates `Path { a: __self_0, .. } => { reset_mut(__self_0); .. }`.
+fn reset_mut_arm(path: &Path, fields: &Fields) -> syn::Result<TokenStream2> {
+    let mut members = Vec::new();
+    let mut bindings = Vec::new();
+    let mut statements = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if let Some(with) = &attrs.with {
+            let message = "`with` is not supported by `ResettableMut`";
+            return Err(syn::Error::new_spanned(with, message));
+        }
+        if attrs.skip {
+            continue;
+        }
+        let binding = format_ident!("__self_{}", index);
+        let ty = &field.ty;
+        statements.push(quote_spanned! {ty.span()=>
+            <#ty as ::resettable::ResettableMut>::reset_mut(#binding);
+        });
+        members.push(match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        });
+        bindings.push(binding);
+    }
+
+    Ok(quote! {
+        #path { #(#members: #bindings,)* .. } => { #(#statements)* },
+    })
+}
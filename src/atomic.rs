@@ -0,0 +1,155 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+//! Lock-free resettable integers. Each type holds the current value and the
+//! last committed one, both accessed through `&self`.
+//!
+//! Loads use `Acquire`, stores use `Release` and `fetch_add` uses `AcqRel`
+//! ordering, so a value observed through `load` happens-after the write that
+//! produced it. `commit` and `reset` copy one atomic into the other with a
+//! separate load and store, a store racing with them may be lost or survive
+//! the reset.
+
+use core::{
+    fmt,
+    sync::atomic::Ordering::{AcqRel, Acquire, Release},
+};
+
+macro_rules! atomic_resettable {
+    ($(#[$cfg:meta] $name:ident($atomic:ident, $ty:ty);)*) => {
+        $(
+            #[$cfg]
+            #[derive(Default)]
+            pub struct $name {
+                current: core::sync::atomic::$atomic,
+                committed: core::sync::atomic::$atomic,
+            }
+
+            #[$cfg]
+            impl $name {
+                pub const fn new(value: $ty) -> Self {
+                    $name {
+                        current: core::sync::atomic::$atomic::new(value),
+                        committed: core::sync::atomic::$atomic::new(value),
+                    }
+                }
+
+                pub fn load(&self) -> $ty {
+                    self.current.load(Acquire)
+                }
+
+                pub fn store(&self, value: $ty) {
+                    self.current.store(value, Release)
+                }
+
+                /// Adds to the current value, wrapping around on overflow,
+                /// and returns the previous value.
+                pub fn fetch_add(&self, value: $ty) -> $ty {
+                    self.current.fetch_add(value, AcqRel)
+                }
+
+                /// The value `reset` returns to.
+                pub fn committed(&self) -> $ty {
+                    self.committed.load(Acquire)
+                }
+
+                pub fn commit(&self) {
+                    self.committed.store(self.current.load(Acquire), Release)
+                }
+
+                pub fn reset(&self) {
+                    self.current.store(self.committed.load(Acquire), Release)
+                }
+
+                pub fn is_dirty(&self) -> bool {
+                    self.load() != self.committed()
+                }
+
+                pub fn into_inner(self) -> $ty {
+                    self.current.into_inner()
+                }
+            }
+
+            #[$cfg]
+            impl From<$ty> for $name {
+                fn from(value: $ty) -> Self {
+                    $name::new(value)
+                }
+            }
+
+            #[$cfg]
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.load().fmt(f)
+                }
+            }
+        )*
+    };
+}
+
+atomic_resettable! {
+    #[cfg(target_has_atomic = "ptr")] AtomicResettableUsize(AtomicUsize, usize);
+    #[cfg(target_has_atomic = "64")] AtomicResettableU64(AtomicU64, u64);
+    #[cfg(target_has_atomic = "64")] AtomicResettableI64(AtomicI64, i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::{AtomicResettableI64, AtomicResettableU64, AtomicResettableUsize};
+
+    #[test]
+    fn commit_reset() {
+        let value = AtomicResettableI64::new(-1);
+        value.store(5);
+        assert!(value.is_dirty());
+        value.reset();
+        assert_eq!(value.load(), -1);
+
+        value.store(5);
+        value.commit();
+        value.store(6);
+        value.reset();
+        assert_eq!(value.load(), 5);
+        assert!(!value.is_dirty());
+
+        let value = AtomicResettableU64::from(u64::MAX);
+        assert_eq!(value.fetch_add(1), u64::MAX);
+        assert_eq!(value.into_inner(), 0);
+    }
+
+    #[test]
+    fn concurrent() {
+        let counter = Arc::new(AtomicResettableUsize::new(0));
+        let threads = (0..8)
+            .map(|_| {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        counter.fetch_add(1);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        assert_eq!(counter.load(), 8000);
+
+        counter.commit();
+        let threads = (0..8)
+            .map(|_| {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        counter.fetch_add(1);
+                        counter.reset();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        counter.reset();
+        assert_eq!(counter.load(), 8000);
+        assert_eq!(counter.committed(), 8000);
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::ResettableWrapper;
+
+impl<T> ResettableWrapper<T> {
+    /// Encodes the whole state with `bincode`, so unlike the serde impl a
+    /// dirty wrapper comes back dirty from `from_bytes`.
+    pub fn to_bytes(&self) -> bincode::Result<Vec<u8>>
+    where
+        T: Serialize,
+    {
+        bincode::serialize(&(&self.inner, &self.stash, &self.initial))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> bincode::Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let (inner, stash, initial) = bincode::deserialize(bytes)?;
+        Ok(ResettableWrapper {
+            inner,
+            stash,
+            initial,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResettableWrapper;
+
+    #[test]
+    fn round_trip() {
+        let clean = ResettableWrapper::new("foo".to_string());
+        let bytes = clean.to_bytes().unwrap();
+        let decoded = ResettableWrapper::<String>::from_bytes(&bytes).unwrap();
+        assert!(decoded.is_clean());
+        assert_eq!(decoded, clean);
+
+        let mut dirty = clean.commit();
+        dirty.push_str("bar");
+        let bytes = dirty.to_bytes().unwrap();
+        let decoded = ResettableWrapper::<String>::from_bytes(&bytes).unwrap();
+        assert_eq!(*decoded, "foobar");
+        assert_eq!(decoded.reset_inner(), "foo");
+
+        assert!(ResettableWrapper::<String>::from_bytes(&bytes[..4]).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    cell::{Ref, RefCell, RefMut},
+    fmt,
+};
+
+use super::{Resettable, ResettableWrapper};
+
+/// A `ResettableWrapper` behind a `RefCell`, so it can be mutated and reset
+/// through a shared reference, e.g. from behind an `Rc`.
+///
+/// Borrowing follows the `RefCell` rules: `borrow` panics while a mutable
+/// borrow is active, while `borrow_mut`, `reset_shared` and `commit_shared`
+/// panic while any other borrow is active.
+///
+/// The shared methods are not called `reset` and `commit`, since with
+/// `Resettable` in scope `cell.reset()` would resolve to the consuming trait
+/// method, or for an `Rc<ResettableCell<T>>` to the one of `Rc`, which resets
+/// a clone.
+#[derive(Default, Clone)]
+pub struct ResettableCell<T>(RefCell<ResettableWrapper<T>>);
+
+impl<T> ResettableCell<T> {
+    pub fn new(inner: T) -> Self {
+        ResettableCell(RefCell::new(ResettableWrapper::new(inner)))
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0.into_inner().into_inner()
+    }
+
+    pub fn reset_inner(self) -> T {
+        self.0.into_inner().reset_inner()
+    }
+
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref::map(self.0.borrow(), |wrapper| &**wrapper)
+    }
+
+    /// Stashes the current value on the first mutable borrow, like
+    /// `ResettableWrapper::deref_mut`.
+    pub fn borrow_mut(&self) -> RefMut<'_, T>
+    where
+        T: Clone,
+    {
+        RefMut::map(self.0.borrow_mut(), |wrapper| &mut **wrapper)
+    }
+
+    pub fn reset_shared(&self) {
+        self.0.borrow_mut().reset_in_place()
+    }
+
+    pub fn commit_shared(&self) {
+        self.0.borrow_mut().commit_in_place()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.0.borrow().is_dirty()
+    }
+}
+
+impl<T> From<T> for ResettableCell<T> {
+    fn from(inner: T) -> Self {
+        ResettableCell::new(inner)
+    }
+}
+
+impl<T> Resettable for ResettableCell<T> {
+    fn reset(self) -> Self {
+        ResettableCell(RefCell::new(self.0.into_inner().reset()))
+    }
+}
+
+impl<T> fmt::Debug for ResettableCell<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.try_borrow() {
+            Ok(wrapper) => wrapper.fmt(f),
+            Err(_) => f.write_str("<borrowed>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{Resettable, ResettableCell};
+
+    #[test]
+    fn shared() {
+        let cell = Rc::new(ResettableCell::new(vec![1]));
+        let other = cell.clone();
+        assert!(!cell.is_dirty());
+
+        other.borrow_mut().push(2);
+        assert!(cell.is_dirty());
+        assert_eq!(*cell.borrow(), [1, 2]);
+
+        cell.reset_shared();
+        assert!(!other.is_dirty());
+        assert_eq!(*other.borrow(), [1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_borrow() {
+        let cell = ResettableCell::new(1);
+        let _guard = cell.borrow();
+        cell.reset_shared();
+    }
+
+    #[test]
+    fn shared_methods_with_trait() {
+        let cell = ResettableCell::new(vec![1]);
+        cell.borrow_mut().push(2);
+        cell.reset_shared();
+        cell.borrow_mut().push(3);
+        cell.commit_shared();
+        assert!(!cell.is_dirty());
+        let cell = cell.reset();
+        assert_eq!(*cell.borrow(), [1, 3]);
+
+        let shared = Rc::new(cell);
+        shared.borrow_mut().push(4);
+        shared.reset_shared();
+        assert!(!shared.is_dirty());
+        assert_eq!(*shared.borrow(), [1, 3]);
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+    fmt, mem,
+};
+
+use super::{Resettable, ResettableMut};
+
+/// A `ResettableWrapper` for `Copy` values that keeps the original in a
+/// plain field next to a `bool` instead of an `Option`, which is smaller for
+/// types without a niche, e.g. integers. It has no separate initial baseline.
+#[derive(Clone, Copy)]
+pub struct CompactResettable<T>
+where
+    T: Copy,
+{
+    inner: T,
+    // Equals `inner` while clean.
+    stash: T,
+    dirty: bool,
+}
+
+impl<T> CompactResettable<T>
+where
+    T: Copy,
+{
+    pub fn new(inner: T) -> Self {
+        CompactResettable {
+            inner,
+            stash: inner,
+            dirty: false,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn reset_inner(self) -> T {
+        self.stash
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn is_clean(&self) -> bool {
+        !self.dirty
+    }
+
+    pub fn original(&self) -> Option<&T> {
+        self.dirty.then(|| &self.stash)
+    }
+
+    /// Accepts the current value as the new baseline, so a later reset
+    /// returns to it.
+    #[must_use = "use `commit_in_place` to commit through a reference"]
+    pub fn commit(mut self) -> Self {
+        self.commit_in_place();
+        self
+    }
+
+    pub fn commit_in_place(&mut self) {
+        self.stash = self.inner;
+        self.dirty = false;
+    }
+
+    pub fn reset_in_place(&mut self) {
+        self.inner = self.stash;
+        self.dirty = false;
+    }
+
+    /// Sets a new value and returns the previous one. A reset returns to the
+    /// previous value if the wrapper was clean.
+    pub fn replace(&mut self, value: T) -> T {
+        self.dirty = true;
+        mem::replace(&mut self.inner, value)
+    }
+
+    /// Passes the current value to `f` for mutation. The wrapper becomes
+    /// dirty even if `f` leaves the value as is.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.deref_mut())
+    }
+
+    /// Takes the current value, leaving `T::default()` behind.
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+
+    /// Resets the wrapper only if `pred` holds for the current value.
+    #[must_use]
+    pub fn reset_if(self, pred: impl FnOnce(&T) -> bool) -> Self {
+        if pred(&self.inner) {
+            self.reset()
+        } else {
+            self
+        }
+    }
+
+    /// Applies `f` to both the current and the original value, so the result
+    /// is dirty if and only if `self` is.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> CompactResettable<U>
+    where
+        U: Copy,
+    {
+        let inner = f(self.inner);
+        CompactResettable {
+            inner,
+            stash: if self.dirty { f(self.stash) } else { inner },
+            dirty: self.dirty,
+        }
+    }
+}
+
+impl<T> Default for CompactResettable<T>
+where
+    T: Copy + Default,
+{
+    fn default() -> Self {
+        CompactResettable::new(T::default())
+    }
+}
+
+impl<T> From<T> for CompactResettable<T>
+where
+    T: Copy,
+{
+    fn from(inner: T) -> Self {
+        CompactResettable::new(inner)
+    }
+}
+
+impl<T> Deref for CompactResettable<T>
+where
+    T: Copy,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for CompactResettable<T>
+where
+    T: Copy,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dirty = true;
+        &mut self.inner
+    }
+}
+
+impl<T> Resettable for CompactResettable<T>
+where
+    T: Copy,
+{
+    fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+}
+
+impl<T> ResettableMut for CompactResettable<T>
+where
+    T: Copy,
+{
+    fn reset_mut(&mut self) {
+        self.reset_in_place()
+    }
+}
+
+// Sound because `Eq`, `Ord` and `Hash` look at the inner value only.
+impl<T> Borrow<T> for CompactResettable<T>
+where
+    T: Copy,
+{
+    fn borrow(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T, U> AsRef<U> for CompactResettable<T>
+where
+    T: Copy + AsRef<U>,
+    U: ?Sized,
+{
+    fn as_ref(&self) -> &U {
+        self.inner.as_ref()
+    }
+}
+
+impl<T> PartialEq for CompactResettable<T>
+where
+    T: Copy + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T> Eq for CompactResettable<T> where T: Copy + Eq {}
+
+impl<T> PartialEq<T> for CompactResettable<T>
+where
+    T: Copy + PartialEq,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.inner == *other
+    }
+}
+
+impl<T> PartialOrd for CompactResettable<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<T> Ord for CompactResettable<T>
+where
+    T: Copy + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<T> Hash for CompactResettable<T>
+where
+    T: Copy + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for CompactResettable<T>
+where
+    T: Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> fmt::Display for CompactResettable<T>
+where
+    T: Copy + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use super::{CompactResettable, Resettable};
+    use crate::ResettableWrapper;
+
+    #[test]
+    fn size() {
+        assert_eq!(mem::size_of::<CompactResettable<u32>>(), 12);
+        assert_eq!(mem::size_of::<CompactResettable<u64>>(), 24);
+        assert!(
+            mem::size_of::<CompactResettable<u32>>() < mem::size_of::<ResettableWrapper<u32>>()
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut value = CompactResettable::new(1u32);
+        assert_eq!(value.original(), None);
+
+        *value += 1;
+        assert!(value.is_dirty());
+        assert_eq!(value.original(), Some(&1));
+        assert_eq!(value.reset(), CompactResettable::new(1));
+
+        value.commit_in_place();
+        assert!(value.is_clean());
+        *value = 3;
+        value.reset_in_place();
+        assert_eq!(value.into_inner(), 2);
+    }
+
+    #[test]
+    fn edits() {
+        let mut value = CompactResettable::new(1u32);
+        assert_eq!(value.replace(2), 1);
+        assert_eq!(value.take(), 2);
+        value.modify(|value| *value += 5);
+        assert_eq!(value, 5);
+
+        let mapped = value.map(|value| value * 10);
+        assert_eq!(mapped.original(), Some(&10));
+        assert_eq!(value.reset_if(|&value| value > 10), 5);
+        assert_eq!(value.commit().reset(), 5);
+        assert_eq!(CompactResettable::new(1u32).map(u64::from).original(), None);
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    ops::{Deref, DerefMut},
+    fmt,
+};
+
+use super::Resettable;
+
+/// A wrapper that resets to `T::default()` rather than to the value it held
+/// before the first mutation.
+///
+/// Nothing is stashed, so unlike `ResettableWrapper` it doesn't need
+/// `T: Clone` to be mutated, at the cost of forgetting the original value.
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResettableDefault<T>(T);
+
+impl<T> Deref for ResettableDefault<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ResettableDefault<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> ResettableDefault<T> {
+    pub fn new(inner: T) -> Self {
+        ResettableDefault(inner)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    pub fn reset_in_place(&mut self)
+    where
+        T: Default,
+    {
+        self.0 = T::default();
+    }
+}
+
+impl<T> From<T> for ResettableDefault<T> {
+    fn from(inner: T) -> Self {
+        ResettableDefault::new(inner)
+    }
+}
+
+impl<T> Resettable for ResettableDefault<T>
+where
+    T: Default,
+{
+    fn reset(self) -> Self {
+        ResettableDefault::default()
+    }
+}
+
+impl<T> fmt::Debug for ResettableDefault<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResettableDefault, Resettable};
+
+    #[derive(Default, Debug, PartialEq)]
+    struct Connection {
+        retries: u32,
+    }
+
+    #[test]
+    fn reset_to_default() {
+        let mut value = ResettableDefault::new(Connection { retries: 3 });
+        value.retries += 1;
+        assert_eq!(value.retries, 4);
+        assert_eq!(value.reset().into_inner(), Connection::default());
+
+        let mut value = ResettableDefault::new(Connection { retries: 3 });
+        value.reset_in_place();
+        assert_eq!(*value, Connection::default());
+    }
+}
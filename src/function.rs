@@ -0,0 +1,90 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use alloc::boxed::Box;
+
+use super::{Resettable, ResettableWrapper};
+
+type Function<A, O> = Box<dyn Fn(A) -> O>;
+type Factory<P, A, O> = Box<dyn Fn(&P) -> Function<A, O>>;
+
+/// A function built from resettable parameters.
+///
+/// `factory` turns the parameters into the function that `call` runs. It is
+/// rebuilt after every `tune` and on reset, so resetting the parameters
+/// brings the original behavior back.
+pub struct ResettableFn<P, A, O> {
+    params: ResettableWrapper<P>,
+    factory: Factory<P, A, O>,
+    function: Function<A, O>,
+}
+
+impl<P, A, O> ResettableFn<P, A, O> {
+    pub fn new(params: P, factory: impl Fn(&P) -> Function<A, O> + 'static) -> Self {
+        let function = factory(&params);
+        ResettableFn {
+            params: ResettableWrapper::new(params),
+            factory: Box::new(factory),
+            function,
+        }
+    }
+
+    pub fn call(&self, args: A) -> O {
+        (self.function)(args)
+    }
+
+    pub fn params(&self) -> &ResettableWrapper<P> {
+        &self.params
+    }
+
+    /// Changes the parameters and rebuilds the function.
+    pub fn tune(&mut self, f: impl FnOnce(&mut P))
+    where
+        P: Clone,
+    {
+        self.params.modify(f);
+        self.function = (self.factory)(&self.params);
+    }
+
+    /// Restores the parameters and rebuilds the function if they changed.
+    pub fn reset_in_place(&mut self) {
+        if self.params.is_dirty() {
+            self.params.reset_in_place();
+            self.function = (self.factory)(&self.params);
+        }
+    }
+}
+
+impl<P, A, O> Resettable for ResettableFn<P, A, O> {
+    fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Resettable, ResettableFn};
+
+    #[test]
+    fn tune_and_reset() {
+        #[derive(Clone)]
+        struct Gain {
+            factor: i32,
+        }
+
+        let mut scale = ResettableFn::new(Gain { factor: 2 }, |gain: &Gain| {
+            let factor = gain.factor;
+            Box::new(move |x: i32| x * factor)
+        });
+        assert_eq!(scale.call(3), 6);
+
+        scale.tune(|gain| gain.factor = 10);
+        assert_eq!(scale.call(3), 30);
+        assert!(scale.params().is_dirty());
+
+        let scale = scale.reset();
+        assert_eq!(scale.call(3), 6);
+        assert_eq!(scale.params().factor, 2);
+    }
+}
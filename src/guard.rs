@@ -0,0 +1,197 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::ops::{Deref, DerefMut};
+
+use super::ResettableWrapper;
+
+/// A scoped edit of a `ResettableWrapper`, created by `edit`.
+///
+/// Dropping the guard rolls back every change made through it, unless
+/// `commit` was called. Changes made before `edit` are kept either way.
+pub struct ResettableGuard<'a, T>
+where
+    T: Clone,
+{
+    wrapper: &'a mut ResettableWrapper<T>,
+    // The value at the time of `edit`, cloned on the first write, needed only
+    // if the wrapper was dirty already, otherwise the stash has it.
+    before: Option<T>,
+    was_dirty: bool,
+    committed: bool,
+}
+
+impl<'a, T> ResettableGuard<'a, T>
+where
+    T: Clone,
+{
+    pub(crate) fn new(wrapper: &'a mut ResettableWrapper<T>) -> Self {
+        ResettableGuard {
+            was_dirty: wrapper.is_dirty(),
+            wrapper,
+            before: None,
+            committed: false,
+        }
+    }
+
+    /// Keeps the changes. The wrapper stays dirty, so resetting it still
+    /// returns to the value before its first mutation.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl<T> Deref for ResettableGuard<'_, T>
+where
+    T: Clone,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.wrapper.inner
+    }
+}
+
+impl<T> DerefMut for ResettableGuard<'_, T>
+where
+    T: Clone,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.was_dirty && self.before.is_none() {
+            self.before = Some(self.wrapper.snapshot());
+        }
+
+        self.wrapper.deref_mut()
+    }
+}
+
+impl<T> Drop for ResettableGuard<'_, T>
+where
+    T: Clone,
+{
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if !self.was_dirty {
+            self.wrapper.reset_in_place();
+        } else if let Some(before) = self.before.take() {
+            self.wrapper.inner = before;
+        }
+    }
+}
+
+/// A mutable borrow of a `ResettableWrapper`, created by `edit_normalized`,
+/// that calls `normalize` when dropped. Editing the value back to the
+/// original leaves the wrapper clean. Unlike `ResettableGuard`, changes are
+/// kept.
+pub struct ResettableNormalizingGuard<'a, T>
+where
+    T: Clone + PartialEq,
+{
+    wrapper: &'a mut ResettableWrapper<T>,
+}
+
+impl<'a, T> ResettableNormalizingGuard<'a, T>
+where
+    T: Clone + PartialEq,
+{
+    pub(crate) fn new(wrapper: &'a mut ResettableWrapper<T>) -> Self {
+        ResettableNormalizingGuard { wrapper }
+    }
+}
+
+impl<T> Deref for ResettableNormalizingGuard<'_, T>
+where
+    T: Clone + PartialEq,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.wrapper.inner
+    }
+}
+
+impl<T> DerefMut for ResettableNormalizingGuard<'_, T>
+where
+    T: Clone + PartialEq,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.wrapper.deref_mut()
+    }
+}
+
+impl<T> Drop for ResettableNormalizingGuard<'_, T>
+where
+    T: Clone + PartialEq,
+{
+    fn drop(&mut self) {
+        self.wrapper.normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ResettableWrapper;
+
+    #[test]
+    fn commit() {
+        let mut wrapper = ResettableWrapper::new(1);
+        let mut guard = wrapper.edit();
+        *guard += 1;
+        guard.commit();
+
+        assert_eq!(*wrapper, 2);
+        assert_eq!(wrapper.original(), Some(&1));
+    }
+
+    #[test]
+    fn rollback() {
+        let mut wrapper = ResettableWrapper::new(1);
+        {
+            let mut guard = wrapper.edit();
+            *guard += 1;
+            assert_eq!(*guard, 2);
+        }
+        assert_eq!(*wrapper, 1);
+        assert!(wrapper.is_clean());
+
+        *wrapper = 2;
+        {
+            let mut guard = wrapper.edit();
+            *guard = 3;
+        }
+        assert_eq!(*wrapper, 2);
+        assert_eq!(wrapper.original(), Some(&1));
+    }
+
+    #[test]
+    fn rollback_on_panic() {
+        let mut wrapper = ResettableWrapper::new(1);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut guard = wrapper.edit();
+            *guard = 2;
+            panic!("edit failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(*wrapper, 1);
+    }
+
+    #[test]
+    fn normalizing() {
+        let mut wrapper = ResettableWrapper::new("foo".to_string());
+        {
+            let mut guard = wrapper.edit_normalized();
+            guard.push_str("bar");
+            guard.truncate(3);
+        }
+        assert!(wrapper.is_clean());
+
+        wrapper.edit_normalized().push('!');
+        assert!(wrapper.is_dirty());
+        assert_eq!(*wrapper, "foo!");
+
+        wrapper.edit_normalized().pop();
+        assert!(wrapper.is_clean());
+    }
+}
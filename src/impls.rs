@@ -0,0 +1,585 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+//! `Resettable` for standard types. Containers are reset element-wise: every
+//! element is passed through `reset` and the container is rebuilt.
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+use core::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+};
+
+use super::{Resettable, ResettableMut};
+
+// Types without state are their own reset, so structs carrying them can
+// derive the traits.
+
+impl Resettable for () {
+    fn reset(self) -> Self {}
+}
+
+// A bare primitive has no record of an earlier value, it resets to itself.
+// This lets derived structs mix plain and wrapped fields.
+macro_rules! identity_impl {
+    ($($ty:ty)*) => {
+        $(
+            impl Resettable for $ty {
+                #[inline]
+                fn reset(self) -> Self {
+                    self
+                }
+            }
+
+            impl ResettableMut for $ty {
+                #[inline]
+                fn reset_mut(&mut self) {}
+            }
+        )*
+    };
+}
+
+identity_impl!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 bool char);
+identity_impl!(NonZeroU8 NonZeroU16 NonZeroU32 NonZeroU64 NonZeroU128 NonZeroUsize);
+identity_impl!(NonZeroI8 NonZeroI16 NonZeroI32 NonZeroI64 NonZeroI128 NonZeroIsize);
+#[cfg(feature = "alloc")]
+identity_impl!(String);
+
+// `Saturating` needs Rust 1.74, newer than the minimum supported version.
+
+impl<T> Resettable for Wrapping<T> {
+    #[inline]
+    fn reset(self) -> Self {
+        self
+    }
+}
+
+impl<T> ResettableMut for Wrapping<T> {
+    #[inline]
+    fn reset_mut(&mut self) {}
+}
+
+impl<T> Resettable for PhantomData<T>
+where
+    T: ?Sized,
+{
+    fn reset(self) -> Self {
+        self
+    }
+}
+
+impl ResettableMut for () {
+    fn reset_mut(&mut self) {}
+}
+
+impl<T> ResettableMut for PhantomData<T>
+where
+    T: ?Sized,
+{
+    fn reset_mut(&mut self) {}
+}
+
+impl<T> Resettable for Option<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.map(Resettable::reset)
+    }
+}
+
+/// Resets the `Ok` value, an `Err` is passed through untouched.
+impl<T, E> Resettable for Result<T, E>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.map(Resettable::reset)
+    }
+}
+
+impl<T, const N: usize> Resettable for [T; N]
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.map(Resettable::reset)
+    }
+}
+
+/// Resets every element, the length is kept. A `ResettableWrapper<Vec<T>>`,
+/// in contrast, rolls back the vec as a whole, including pushes and pops.
+///
+/// ```
+/// use resettable::{Resettable, ResettableWrapper};
+///
+/// let mut items = vec![ResettableWrapper::new(1), ResettableWrapper::new(2)];
+/// *items[0] = 10;
+/// items.push(ResettableWrapper::new(3));
+/// assert_eq!(items.reset(), [1, 2, 3]);
+///
+/// let mut list = ResettableWrapper::new(vec![1, 2]);
+/// list[0] = 10;
+/// list.push(3);
+/// assert_eq!(list.reset_inner(), [1, 2]);
+/// ```
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Vec<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(Resettable::reset).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, T, S> Resettable for HashMap<K, T, S>
+where
+    K: Eq + Hash,
+    T: Resettable,
+    S: BuildHasher + Default,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(|(k, v)| (k, v.reset())).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, T> Resettable for BTreeMap<K, T>
+where
+    K: Ord,
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(|(k, v)| (k, v.reset())).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for VecDeque<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(Resettable::reset).collect()
+    }
+}
+
+// Sets and heaps are rebuilt by reinserting the reset elements, so the order
+// follows the reset values and elements that reset to equal values collapse
+// into one in a set.
+
+#[cfg(feature = "std")]
+impl<T, S> Resettable for HashSet<T, S>
+where
+    T: Resettable + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(Resettable::reset).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for BTreeSet<T>
+where
+    T: Resettable + Ord,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(Resettable::reset).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for BinaryHeap<T>
+where
+    T: Resettable + Ord,
+{
+    fn reset(self) -> Self {
+        self.into_iter().map(Resettable::reset).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Box<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        Box::new((*self).reset())
+    }
+}
+
+/// Moves the elements through a `Vec` and back, which may reallocate.
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Box<[T]>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        self.into_vec().into_iter().map(Resettable::reset).collect()
+    }
+}
+
+// A uniquely owned value is moved out and reset, a shared one is cloned first,
+// so the other owners keep seeing the value they had.
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Rc<T>
+where
+    T: Resettable + Clone,
+{
+    fn reset(self) -> Self {
+        let inner = Rc::try_unwrap(self).unwrap_or_else(|rc| (*rc).clone());
+        Rc::new(inner.reset())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Arc<T>
+where
+    T: Resettable + Clone,
+{
+    fn reset(self) -> Self {
+        let inner = Arc::try_unwrap(self).unwrap_or_else(|arc| (*arc).clone());
+        Arc::new(inner.reset())
+    }
+}
+
+impl<T> ResettableMut for Option<T>
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        if let Some(value) = self {
+            value.reset_mut()
+        }
+    }
+}
+
+impl<T> ResettableMut for [T]
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        self.iter_mut().for_each(ResettableMut::reset_mut)
+    }
+}
+
+impl<T, const N: usize> ResettableMut for [T; N]
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        self[..].reset_mut()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ResettableMut for Vec<T>
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        self[..].reset_mut()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ResettableMut for Box<T>
+where
+    T: ResettableMut + ?Sized,
+{
+    fn reset_mut(&mut self) {
+        (**self).reset_mut()
+    }
+}
+
+// The cell is consumed or mutably borrowed, so neither `Copy` nor a runtime
+// borrow check is needed.
+
+impl<T> Resettable for Cell<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        Cell::new(self.into_inner().reset())
+    }
+}
+
+impl<T> Resettable for RefCell<T>
+where
+    T: Resettable,
+{
+    fn reset(self) -> Self {
+        RefCell::new(self.into_inner().reset())
+    }
+}
+
+impl<T> ResettableMut for Cell<T>
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        self.get_mut().reset_mut()
+    }
+}
+
+impl<T> ResettableMut for RefCell<T>
+where
+    T: ResettableMut,
+{
+    fn reset_mut(&mut self) {
+        self.get_mut().reset_mut()
+    }
+}
+
+// A shared slice can't be moved out of, so the elements are always cloned
+// into a new allocation, even if the slice is uniquely owned.
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Rc<[T]>
+where
+    T: Resettable + Clone,
+{
+    fn reset(self) -> Self {
+        self.iter().cloned().map(Resettable::reset).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Resettable for Arc<[T]>
+where
+    T: Resettable + Clone,
+{
+    fn reset(self) -> Self {
+        self.iter().cloned().map(Resettable::reset).collect()
+    }
+}
+
+macro_rules! tuple_impl {
+    ($($name:ident)+) => {
+        impl<$($name),+> Resettable for ($($name,)+)
+        where
+            $($name: Resettable,)+
+        {
+            #[allow(non_snake_case)]
+            fn reset(self) -> Self {
+                let ($($name,)+) = self;
+                ($($name.reset(),)+)
+            }
+        }
+    };
+}
+
+tuple_impl!(T0);
+tuple_impl!(T0 T1);
+tuple_impl!(T0 T1 T2);
+tuple_impl!(T0 T1 T2 T3);
+tuple_impl!(T0 T1 T2 T3 T4);
+tuple_impl!(T0 T1 T2 T3 T4 T5);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6 T7);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6 T7 T8);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+tuple_impl!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::super::{ResettableWrapper, Resettable};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collections() {
+        let mut vec = vec![ResettableWrapper::new(1), ResettableWrapper::new(2)];
+        *vec[1] += 10;
+        assert_eq!(vec.reset(), [1, 2]);
+
+        let mut map = HashMap::new();
+        map.insert("a", ResettableWrapper::new(1));
+        map.insert("b", ResettableWrapper::new(2));
+        **map.get_mut("a").unwrap() = 5;
+        let map = map.reset();
+        assert_eq!(*map["a"], 1);
+        assert!(map.values().all(ResettableWrapper::is_clean));
+
+        let mut map = BTreeMap::new();
+        map.insert(1, ResettableWrapper::new("foo".to_string()));
+        map.get_mut(&1).unwrap().push_str("bar");
+        assert_eq!(*map.reset()[&1], "foo");
+
+        let mut option = Some(ResettableWrapper::new(1));
+        if let Some(value) = &mut option {
+            **value = 2;
+        }
+        assert_eq!(option.reset(), Some(1.into()));
+        assert_eq!(None::<ResettableWrapper<i32>>.reset(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn queues_and_sets() {
+        use std::collections::{BTreeSet, BinaryHeap, HashSet, VecDeque};
+
+        let dirty = |current, original| ResettableWrapper::new_dirty(current, original);
+
+        let mut deque = VecDeque::from([ResettableWrapper::new(1), ResettableWrapper::new(2)]);
+        *deque[0] = 5;
+        deque.push_front(dirty(7, 0));
+        assert_eq!(deque.reset(), [0, 1, 2]);
+
+        let set = HashSet::from([dirty(5, 1), dirty(6, 2), ResettableWrapper::new(3)]);
+        let set = set.reset();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&2) && set.iter().all(ResettableWrapper::is_clean));
+
+        let set = BTreeSet::from([dirty(5, 3), dirty(6, 1), ResettableWrapper::new(3)]);
+        assert!(set.reset().into_iter().eq([1, 3]));
+
+        let heap = BinaryHeap::from([dirty(9, 1), dirty(8, 2), ResettableWrapper::new(3)]);
+        assert_eq!(heap.reset().into_sorted_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn cells() {
+        use core::cell::{Cell, RefCell};
+
+        use crate::{CompactResettable, ResettableMut};
+
+        let cell = Cell::new(CompactResettable::new(1));
+        let mut value = cell.get();
+        *value = 2;
+        cell.set(value);
+        let mut cell = cell.reset();
+        assert_eq!(*cell.get(), 1);
+
+        **cell.get_mut() = 3;
+        cell.reset_mut();
+        assert!(cell.get().is_clean());
+
+        let cell = RefCell::new(ResettableWrapper::new(vec![1]));
+        cell.borrow_mut().push(2);
+        let cell = cell.reset();
+        assert_eq!(**cell.borrow(), [1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn slices() {
+        use std::rc::Rc;
+
+        let mut items = vec![ResettableWrapper::new(1), ResettableWrapper::new(2)];
+        *items[1] = 3;
+        let items = items.into_boxed_slice();
+        assert_eq!(*items.reset(), [1, 2]);
+
+        let dirty = ResettableWrapper::new_dirty(5, 1);
+        let shared: Rc<[_]> = Rc::from([dirty, ResettableWrapper::new(2)]);
+        let copy = shared.clone();
+        assert_eq!(*shared.reset(), [1, 2]);
+        assert_eq!(*copy, [5, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_layering() {
+        let mut items = vec![ResettableWrapper::new(1), ResettableWrapper::new(2)];
+        items.pop();
+        *items[0] = 3;
+        assert_eq!(items.reset(), [1]);
+
+        let mut items = ResettableWrapper::new(vec![ResettableWrapper::new(1)]);
+        items.push(ResettableWrapper::new(2));
+        *items[0] = 3;
+        assert_eq!(*items.clone().reset(), [1]);
+        assert_eq!(items.into_inner().reset(), [1, 2]);
+    }
+
+    #[test]
+    fn result() {
+        let mut ok = Ok::<_, ResettableWrapper<i32>>(ResettableWrapper::new(1));
+        if let Ok(value) = &mut ok {
+            **value = 2;
+        }
+        assert_eq!(ok.reset(), Ok(1.into()));
+
+        let mut error = ResettableWrapper::new(1);
+        *error = 2;
+        let err = Err::<ResettableWrapper<i32>, _>(error).reset().unwrap_err();
+        assert!(err.is_dirty());
+        assert_eq!(err, 2);
+    }
+
+    #[test]
+    fn arrays() {
+        let mut array = [1, 2, 3, 4].map(ResettableWrapper::new);
+        *array[1] = 20;
+        *array[3] = 40;
+
+        let array = array.reset();
+        assert_eq!(array, [1, 2, 3, 4]);
+        assert!(array.iter().all(ResettableWrapper::is_clean));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn smart_pointers() {
+        use std::{rc::Rc, sync::Arc};
+
+        let mut boxed = Box::new(ResettableWrapper::new(1));
+        **boxed = 2;
+        assert_eq!(*boxed.reset(), ResettableWrapper::new(1));
+
+        let mut dirty = ResettableWrapper::new(1);
+        *dirty = 2;
+        let rc = Rc::new(dirty.clone());
+        let shared = rc.clone();
+        assert_eq!(**rc.reset(), 1);
+        assert_eq!(**shared, 2);
+        assert!(shared.is_dirty());
+
+        let arc = Arc::new(dirty);
+        assert_eq!(**arc.reset(), 1);
+    }
+
+    #[test]
+    fn tuples() {
+        let mut tuple = (
+            ResettableWrapper::new(1u32),
+            ResettableWrapper::new("foo".to_string()),
+        );
+        *tuple.0 = 2;
+        tuple.1.push_str("bar");
+
+        let (first, second) = tuple.reset();
+        assert_eq!(*first, 1);
+        assert_eq!(*second, "foo");
+
+        let mut single = (ResettableWrapper::new(1),);
+        *single.0 = 2;
+        assert_eq!(*single.reset().0, 1);
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use serde_json::Value;
+
+use super::ResettableWrapper;
+
+impl ResettableWrapper<Value> {
+    /// Restores only the sub-value at the JSON Pointer `pointer` from the
+    /// original, leaving edits elsewhere intact. A sub-value added by the
+    /// edits is removed, a removed one is put back.
+    ///
+    /// Returns `false` if the wrapper is clean or the pointer resolves
+    /// neither in the original nor in the current value. The wrapper is
+    /// clean afterwards if nothing else was changed.
+    pub fn reset_pointer(&mut self, pointer: &str) -> bool {
+        let stash = match &self.stash {
+            Some(stash) => stash,
+            None => return false,
+        };
+        if pointer.is_empty() {
+            self.reset_in_place();
+            return true;
+        }
+        let (parent, key) = match pointer.rsplit_once('/') {
+            Some((parent, key)) => (parent, key.replace("~1", "/").replace("~0", "~")),
+            None => return false,
+        };
+
+        let original = stash.pointer(pointer).cloned();
+        let restored = match (self.inner.pointer_mut(pointer), original) {
+            (Some(current), Some(original)) => {
+                *current = original;
+                true
+            },
+            (None, Some(original)) => match self.inner.pointer_mut(parent) {
+                Some(Value::Object(map)) => {
+                    map.insert(key, original);
+                    true
+                },
+                Some(Value::Array(items)) => match key.parse::<usize>() {
+                    Ok(index) if index <= items.len() => {
+                        items.insert(index, original);
+                        true
+                    },
+                    _ => false,
+                },
+                _ => false,
+            },
+            (Some(_), None) => match self.inner.pointer_mut(parent) {
+                Some(Value::Object(map)) => map.remove(&key).is_some(),
+                Some(Value::Array(items)) => {
+                    let index = key.parse::<usize>().ok().filter(|&i| i < items.len());
+                    index.map(|index| items.remove(index)).is_some()
+                },
+                _ => false,
+            },
+            (None, None) => false,
+        };
+        self.normalize();
+        restored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ResettableWrapper;
+
+    #[test]
+    fn reset_pointer() {
+        let mut doc = ResettableWrapper::new(json!({
+            "name": "foo",
+            "tags": ["a", "b"],
+            "meta": {"a/b": 1},
+        }));
+        assert!(!doc.reset_pointer("/name"));
+
+        doc["name"] = json!("bar");
+        doc["tags"][0] = json!("c");
+        assert!(doc.reset_pointer("/name"));
+        assert_eq!(doc["name"], "foo");
+        assert_eq!(doc["tags"][0], "c");
+
+        assert!(doc.reset_pointer("/tags/0"));
+        assert!(doc.is_clean());
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let mut doc = ResettableWrapper::new(json!({"meta": {"a/b": 1}, "tags": ["a"]}));
+        doc["meta"].as_object_mut().unwrap().remove("a/b");
+        doc["tags"].as_array_mut().unwrap().push(json!("b"));
+        doc["extra"] = json!(true);
+
+        assert!(!doc.reset_pointer("/missing"));
+        assert!(!doc.reset_pointer("no-slash"));
+        assert!(doc.reset_pointer("/meta/a~1b"));
+        assert!(doc.reset_pointer("/tags/1"));
+        assert!(doc.reset_pointer("/extra"));
+        assert!(doc.is_clean());
+        assert_eq!(*doc, json!({"meta": {"a/b": 1}, "tags": ["a"]}));
+    }
+}
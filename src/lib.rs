@@ -2,28 +2,171 @@
 // SPDX-License-Identifier: MIT
 
 #![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
 
 #[cfg(feature = "derive")]
 pub use resettable_derive::*;
 
-use std::{
+// Used by the generated code.
+#[cfg(all(feature = "derive", feature = "alloc"))]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{string::String, vec::Vec};
+}
+
+mod impls;
+
+mod atomic;
+#[cfg(target_has_atomic = "64")]
+pub use self::atomic::{AtomicResettableI64, AtomicResettableU64};
+#[cfg(target_has_atomic = "ptr")]
+pub use self::atomic::AtomicResettableUsize;
+
+mod cell;
+pub use self::cell::ResettableCell;
+
+mod compact;
+pub use self::compact::CompactResettable;
+
+mod default;
+pub use self::default::ResettableDefault;
+
+#[cfg(feature = "alloc")]
+mod function;
+#[cfg(feature = "alloc")]
+pub use self::function::ResettableFn;
+
+mod guard;
+pub use self::guard::{ResettableGuard, ResettableNormalizingGuard};
+
+#[cfg(feature = "bincode")]
+mod bytes;
+
+#[cfg(feature = "json")]
+mod json;
+
+mod policy;
+pub use self::policy::{CloneStash, DefaultStash, ResettableWrapperWith, StashPolicy};
+
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "alloc")]
+pub use self::shared::ResettableShared;
+
+#[cfg(feature = "alloc")]
+mod stack;
+#[cfg(feature = "alloc")]
+pub use self::stack::ResettableStack;
+
+mod watched;
+pub use self::watched::ResettableWatched;
+
+use core::{
+    borrow::Borrow,
     ops::{Deref, DerefMut},
     fmt,
+    hash::{Hash, Hasher},
+    cmp::Ordering,
+    iter::{Product, Sum},
+    mem,
+    time::Duration,
 };
 
+/// Rolls a value back to its baseline.
+///
+/// Implementations must be idempotent: `value.reset().reset()` is the same
+/// as `value.reset()`. The impls of this crate leave a reset value clean, so
+/// a second reset has nothing to roll back.
 pub trait Resettable {
+    #[must_use = "`reset` returns the reset value, `reset_mut` works in place"]
     fn reset(self) -> Self;
 }
 
-#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// Resets a value in place, through a mutable reference.
+///
+/// `Resettable::reset` consumes the value, so it can't be implemented for
+/// `&mut T`. This trait is the by-reference counterpart.
+pub trait ResettableMut {
+    fn reset_mut(&mut self);
+}
+
+impl<T> ResettableMut for ResettableWrapper<T> {
+    #[inline]
+    fn reset_mut(&mut self) {
+        self.reset_in_place()
+    }
+}
+
+impl<T> ResettableMut for &mut T
+where
+    T: ResettableMut + ?Sized,
+{
+    fn reset_mut(&mut self) {
+        (**self).reset_mut()
+    }
+}
+
+/// Resets any number of wrappers in place, e.g. `reset_all!(a, self.b)`.
+#[macro_export]
+macro_rules! reset_all {
+    ($($wrapper:expr),* $(,)?) => {{
+        $($wrapper.reset_in_place();)*
+    }};
+}
+
+/// Resets every wrapper of a slice in place.
+pub trait ResettableSliceExt {
+    fn reset_all(&mut self);
+}
+
+impl<T> ResettableSliceExt for [ResettableWrapper<T>] {
+    fn reset_all(&mut self) {
+        self.iter_mut().for_each(ResettableWrapper::reset_in_place)
+    }
+}
+
+/// Tracks a value against two baselines.
+///
+/// The committed baseline is what `reset` returns to. It starts as the
+/// construction value and is moved to the current value by `commit`. The
+/// initial baseline is the construction value itself, `reset_to_initial`
+/// returns to it even after commits.
+///
+/// ```text
+/// new(a)       current a, committed a, initial a, clean
+/// edit to b    current b, committed a, initial a, dirty
+/// commit       current b, committed b, initial a, clean
+/// edit to c    current c, committed b, initial a, dirty
+/// reset        current b, committed b, initial a, clean
+/// reset_to_initial
+///              current a, committed a, initial a, clean
+/// ```
+#[derive(Default, Clone)]
 pub struct ResettableWrapper<T> {
     inner: T,
+    // The committed baseline, if the current value differs from it.
     stash: Option<T>,
+    // The initial baseline, if a commit moved the committed one away from it.
+    initial: Option<T>,
 }
 
+// Method calls auto-deref, so `&self` methods of the inner value, e.g.
+// `len`, `contains` or `iter`, are callable on the wrapper directly and never
+// stash. There is no need for inherent passthroughs.
 impl<T> Deref for ResettableWrapper<T> {
     type Target = T;
 
+    #[inline]
     fn deref(&self) -> &Self::Target {
         &self.inner
     }
@@ -33,6 +176,7 @@ impl<T> DerefMut for ResettableWrapper<T>
 where
     T: Clone,
 {
+    #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         if self.stash.is_none() {
             self.stash = Some(self.inner.clone());
@@ -42,15 +186,193 @@ where
     }
 }
 
+// Stashing a borrowed `Cow` only copies the reference, so a wrapper created
+// from a borrowed default pays for one `to_owned` on the first edit and
+// resets back to the borrowed form. The default has to outlive the wrapper.
+#[cfg(feature = "alloc")]
+impl<'a, B> ResettableWrapper<Cow<'a, B>>
+where
+    B: ToOwned + ?Sized,
+{
+    pub fn from_default(default: &'a B) -> Self {
+        ResettableWrapper::new(Cow::Borrowed(default))
+    }
+
+    /// Returns `true` while the current value is borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.inner, Cow::Borrowed(_))
+    }
+
+    /// Stashes the current value if needed and returns the owned form.
+    pub fn to_mut(&mut self) -> &mut B::Owned {
+        self.deref_mut().to_mut()
+    }
+}
+
+// A plain reset moves the stash in and drops the current buffer. These copy
+// the original into the current buffer instead, so capacity reserved while
+// editing survives the reset.
+
+#[cfg(feature = "alloc")]
+impl<U> ResettableWrapper<Vec<U>>
+where
+    U: Clone,
+{
+    pub fn reset_preserving_capacity(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.inner.clear();
+            self.inner.extend_from_slice(&stash);
+        }
+    }
+
+    /// Restores the elements selected by `pred` from the original at the same
+    /// position. Selected elements past the end of the original are kept, so
+    /// after pushes or removals the positions may no longer line up. The
+    /// wrapper stays dirty.
+    pub fn reset_matching(&mut self, pred: impl Fn(&U) -> bool) {
+        if let Some(stash) = &self.stash {
+            for (current, original) in self.inner.iter_mut().zip(stash) {
+                if pred(current) {
+                    *current = original.clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ResettableWrapper<String> {
+    pub fn reset_preserving_capacity(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.inner.clear();
+            self.inner.push_str(&stash);
+        }
+    }
+}
+
+impl ResettableWrapper<Duration> {
+    /// Stashes the current value if needed and adds `duration`, clamping
+    /// at `Duration::MAX`.
+    pub fn saturating_add(&mut self, duration: Duration) {
+        let sum = self.inner.saturating_add(duration);
+        *self.deref_mut() = sum;
+    }
+}
+
+// Sound because `Eq`, `Ord` and `Hash` look at the inner value only.
+impl<T> Borrow<T> for ResettableWrapper<T> {
+    fn borrow(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> ResettableWrapper<ResettableWrapper<T>> {
+    /// Collapses two layers into one.
+    ///
+    /// `reset` on a double wrapper only rolls back the outer layer. The
+    /// flattened wrapper holds the innermost current value and resets to what
+    /// resetting both layers would give: the original of the outer original
+    /// if the outer layer is dirty, otherwise the inner layer's original.
+    pub fn flatten(self) -> ResettableWrapper<T> {
+        match self.stash {
+            Some(stash) => ResettableWrapper {
+                inner: self.inner.inner,
+                initial: self
+                    .initial
+                    .map(ResettableWrapper::into_initial)
+                    .or(stash.initial),
+                stash: Some(stash.stash.unwrap_or(stash.inner)),
+            },
+            None => ResettableWrapper {
+                initial: self
+                    .initial
+                    .map(ResettableWrapper::into_initial)
+                    .or(self.inner.initial),
+                ..self.inner
+            },
+        }
+    }
+}
+
+// Forwards to the inner value the way `Deref` does, so a wrapped `String`
+// is `AsRef<str>`. Mutable access stashes first.
+
+impl<T, U> AsRef<U> for ResettableWrapper<T>
+where
+    T: AsRef<U>,
+    U: ?Sized,
+{
+    fn as_ref(&self) -> &U {
+        self.inner.as_ref()
+    }
+}
+
+impl<T, U> AsMut<U> for ResettableWrapper<T>
+where
+    T: AsMut<U> + Clone,
+    U: ?Sized,
+{
+    fn as_mut(&mut self) -> &mut U {
+        self.deref_mut().as_mut()
+    }
+}
+
 impl<T> ResettableWrapper<T> {
     pub fn new(inner: T) -> Self {
-        ResettableWrapper { inner, stash: None }
+        ResettableWrapper {
+            inner,
+            stash: None,
+            initial: None,
+        }
+    }
+
+    /// Same as `new`, but usable in `const` and `static` items.
+    pub const fn from_const(inner: T) -> Self {
+        ResettableWrapper {
+            inner,
+            stash: None,
+            initial: None,
+        }
+    }
+
+    /// Creates a wrapper from the current value and the stash, a clean one if
+    /// `stash` is `None`.
+    pub fn from_parts(inner: T, stash: Option<T>) -> Self {
+        ResettableWrapper {
+            inner,
+            stash,
+            initial: None,
+        }
+    }
+
+    /// Splits the wrapper into the current value and the stash. The initial
+    /// baseline, if it was kept apart, is dropped.
+    pub fn into_parts(self) -> (T, Option<T>) {
+        (self.inner, self.stash)
+    }
+
+    /// Creates a dirty wrapper holding `inner` that resets to `original`.
+    pub fn new_dirty(inner: T, original: T) -> Self {
+        ResettableWrapper {
+            inner,
+            stash: Some(original),
+            initial: None,
+        }
     }
 
+    /// Unwraps the current value, discarding the stash.
+    ///
+    /// This is the counterpart of `From<T>`. The reverse conversion
+    /// `impl<T> From<ResettableWrapper<T>> for T` is rejected by the coherence
+    /// rules, so it has to be spelled out; use `reset_inner` to unwrap the
+    /// original value instead.
     pub fn into_inner(self) -> T {
         self.inner
     }
 
+    /// Unwraps the value a reset would yield.
+    #[inline]
+    #[must_use]
     pub fn reset_inner(self) -> T {
         if let Some(stash) = self.stash {
             stash
@@ -58,99 +380,1850 @@ impl<T> ResettableWrapper<T> {
             self.inner
         }
     }
-}
 
-impl<T> From<T> for ResettableWrapper<T> {
-    fn from(inner: T) -> Self {
-        ResettableWrapper::new(inner)
+    /// Borrows through the inner value, e.g. a wrapped `String` as `&str`.
+    pub fn as_deref(&self) -> &T::Target
+    where
+        T: Deref,
+    {
+        &self.inner
     }
-}
 
-impl<T> Resettable for ResettableWrapper<T> {
-    fn reset(self) -> Self {
-        ResettableWrapper::new(self.reset_inner())
+    /// Returns `true` if the value was mutably borrowed since the wrapper
+    /// was created, reset or committed. Any `DerefMut` access counts, even
+    /// one that leaves the value unchanged.
+    ///
+    /// A commit moves the baseline, so the wrapper is clean afterwards even
+    /// though the value may differ from `initial`, which only
+    /// `reset_to_initial` returns to.
+    pub fn is_dirty(&self) -> bool {
+        self.stash.is_some()
     }
-}
 
-impl<T> fmt::Debug for ResettableWrapper<T>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
+    /// The value a reset would restore, if the wrapper is dirty.
+    pub fn original(&self) -> Option<&T> {
+        self.stash.as_ref()
     }
-}
 
-#[cfg(feature = "serde")]
-mod serde_m {
-    use serde::{ser, de};
+    /// The value a reset would restore: the original if dirty, otherwise the
+    /// current value. The borrowing counterpart of `reset_inner`.
+    pub fn rollback_value(&self) -> &T {
+        self.stash.as_ref().unwrap_or(&self.inner)
+    }
 
-    use super::ResettableWrapper;
+    /// Passes the current value and the original, if any, to `f`.
+    pub fn inspect<R>(&self, f: impl FnOnce(&T, Option<&T>) -> R) -> R {
+        f(&self.inner, self.stash.as_ref())
+    }
 
-    impl<T> ser::Serialize for ResettableWrapper<T>
+    /// Returns `true` if the value differs from the original. Unlike
+    /// `is_dirty`, a mutation that restored the original doesn't count.
+    pub fn changed(&self) -> bool
     where
-        T: ser::Serialize,
+        T: PartialEq,
     {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: ser::Serializer,
-        {
-            self.inner.serialize(serializer)
-        }
+        self.diff().is_some()
     }
 
-    impl<'de, T> de::Deserialize<'de> for ResettableWrapper<T>
+    /// The original value, but only if the current value differs from it.
+    pub fn diff(&self) -> Option<&T>
     where
-        T: de::Deserialize<'de>,
+        T: PartialEq,
     {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: de::Deserializer<'de>,
-        {
-            T::deserialize(deserializer).map(ResettableWrapper::new)
+        self.stash.as_ref().filter(|stash| **stash != self.inner)
+    }
+
+    /// Returns `true` if the wrapper is dirty but the value equals the
+    /// original again, i.e. the edits had no net effect.
+    pub fn has_reverted(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.stash.as_ref() == Some(&self.inner)
+    }
+
+    /// Drops the stash if the value was changed back to the original by hand,
+    /// so an edit that ended up a no-op counts as clean again.
+    pub fn normalize(&mut self)
+    where
+        T: PartialEq,
+    {
+        if self.has_reverted() {
+            self.stash = None;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{ResettableWrapper, Resettable};
+    /// The opposite of `is_dirty`.
+    pub fn is_clean(&self) -> bool {
+        !self.is_dirty()
+    }
 
-    #[test]
-    fn basic() {
-        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-        struct Complex {
-            first: ResettableWrapper<usize>,
-            second: ResettableWrapper<String>,
+    /// Accepts the current value as the new baseline, so a later reset
+    /// returns to it.
+    #[must_use = "use `commit_in_place` to commit through a reference"]
+    pub fn commit(mut self) -> Self {
+        self.commit_in_place();
+        self
+    }
+
+    pub fn commit_in_place(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.initial.get_or_insert(stash);
         }
+    }
 
-        impl Resettable for Complex {
-            fn reset(self) -> Self {
-                Complex {
-                    first: self.first.reset(),
-                    second: self.second.reset(),
-                }
-            }
+    /// The construction value, i.e. what `reset_to_initial` would restore.
+    pub fn initial(&self) -> &T {
+        self.initial
+            .as_ref()
+            .unwrap_or_else(|| self.rollback_value())
+    }
+
+    /// Discards the current value and every commit, returning a clean
+    /// wrapper holding the construction value.
+    #[must_use]
+    pub fn reset_to_initial(self) -> Self {
+        ResettableWrapper::new(self.into_initial())
+    }
+
+    fn into_initial(self) -> T {
+        self.initial.or(self.stash).unwrap_or(self.inner)
+    }
+
+    /// Mutable access that bypasses tracking: nothing is stashed and the
+    /// wrapper doesn't become dirty.
+    ///
+    /// Edits made through it are not protected by the stash. On a clean
+    /// wrapper they become part of the baseline, a reset keeps them. On a
+    /// dirty wrapper they are lost on reset, like any other edit.
+    pub fn get_mut_untracked(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Stashes the current value if needed and passes it to `f` for mutation.
+    /// The wrapper becomes dirty even if `f` leaves the value as is, callers
+    /// wanting to avoid that should compare the value before and after.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: Clone,
+    {
+        f(self.deref_mut())
+    }
+
+    /// Applies `f` to both the current and the stashed value, so the result
+    /// is dirty if and only if `self` is, and resets to the mapped original.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> ResettableWrapper<U> {
+        ResettableWrapper {
+            inner: f(self.inner),
+            stash: self.stash.map(&mut f),
+            initial: self.initial.map(f),
         }
+    }
 
-        let original = Complex {
-            first: 123.into(),
-            second: "foo".to_string().into(),
+    /// Combines two wrappers into one, dirty if either of them is. The
+    /// original of a dirty result pairs the original of the dirty side with
+    /// the current value of the clean side, so a reset restores both.
+    pub fn zip<U>(self, other: ResettableWrapper<U>) -> ResettableWrapper<(T, U)>
+    where
+        T: Clone,
+        U: Clone,
+    {
+        let initial = if self.initial.is_some() || other.initial.is_some() {
+            Some((self.initial().clone(), other.initial().clone()))
+        } else {
+            None
         };
-        let mut complex = original.clone();
-        println!("original: {:?}", complex);
+        let stash = match (self.stash, other.stash) {
+            (None, None) => None,
+            (Some(stash), Some(other_stash)) => Some((stash, other_stash)),
+            (Some(stash), None) => Some((stash, other.inner.clone())),
+            (None, Some(other_stash)) => Some((self.inner.clone(), other_stash)),
+        };
+        ResettableWrapper {
+            inner: (self.inner, other.inner),
+            stash,
+            initial,
+        }
+    }
 
-        *complex.first += 100;
-        println!("mutated first: {:?}", complex);
+    /// Sets a new value and returns the previous one. Stashes the previous
+    /// value if the wrapper was clean, so a reset returns to it.
+    pub fn replace(&mut self, value: T) -> T
+    where
+        T: Clone,
+    {
+        let old = mem::replace(&mut self.inner, value);
+        if self.stash.is_none() {
+            self.stash = Some(old.clone());
+        }
+        old
+    }
 
-        let mut complex = complex.reset();
+    /// Replaces the current value with `f` applied to it, stashing it first
+    /// if the wrapper was clean. Like `DerefMut`, it clones only then: `f`
+    /// gets the clone, so a clean wrapper is left intact if it panics. A
+    /// dirty one hands its current value to `f` and is left reset.
+    pub fn replace_with(&mut self, f: impl FnOnce(T) -> T)
+    where
+        T: Clone,
+    {
+        let current = match self.stash.take() {
+            Some(stash) => mem::replace(&mut self.inner, stash),
+            None => self.inner.clone(),
+        };
+        let value = f(current);
+        self.stash = Some(mem::replace(&mut self.inner, value));
+    }
 
-        *complex.second = "bar".to_string();
-        println!("mutated second: {:?}", complex);
+    /// Takes the current value, leaving `T::default()` behind. Like `replace`,
+    /// the taken value is stashed if the wrapper was clean.
+    pub fn take(&mut self) -> T
+    where
+        T: Default + Clone,
+    {
+        self.replace(T::default())
+    }
 
-        let reset = complex.reset();
-        println!("original: {:?}", reset);
+    /// Clones the current value into a new clean wrapper. `clone`, in
+    /// contrast, copies the stash too, so a clone of a dirty wrapper is dirty.
+    pub fn clone_clean(&self) -> Self
+    where
+        T: Clone,
+    {
+        ResettableWrapper::new(self.inner.clone())
+    }
 
-        assert_eq!(reset, original);
+    /// Starts a branch at the current value: the fork is clean, so a reset
+    /// returns to the fork point. Unlike `clone_clean`, the fork keeps the
+    /// initial baseline, so `reset_to_initial` still reaches the value this
+    /// wrapper was created with.
+    pub fn fork(&self) -> Self
+    where
+        T: Clone,
+    {
+        let initial = (self.initial.is_some() || self.is_dirty()).then(|| self.initial().clone());
+        ResettableWrapper {
+            inner: self.inner.clone(),
+            stash: None,
+            initial,
+        }
+    }
+
+    /// Swaps two wrappers whole, values and tracking state, same as
+    /// `mem::swap`. Each value keeps its original.
+    pub fn swap(&mut self, other: &mut Self) {
+        mem::swap(self, other)
+    }
+
+    /// Swaps only the current values, each wrapper keeps its own original,
+    /// so both are dirty afterwards and reset to what they held before.
+    pub fn swap_values(&mut self, other: &mut Self)
+    where
+        T: Clone,
+    {
+        mem::swap(self.deref_mut(), other.deref_mut())
+    }
+
+    /// Returns a copy of the current value to be passed to `restore` later.
+    pub fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.inner.clone()
+    }
+
+    /// Sets the current value to `snap`. This is a tracked mutation: an
+    /// existing stash is kept and a clean wrapper stashes its value first, so
+    /// `reset` still returns to the value before the first mutation.
+    pub fn restore(&mut self, snap: T)
+    where
+        T: Clone,
+    {
+        self.replace(snap);
+    }
+
+    /// Discards both the current and the stashed value and returns a clean
+    /// wrapper holding `value`.
+    #[must_use]
+    pub fn reset_to(self, value: T) -> Self {
+        ResettableWrapper::new(value)
+    }
+
+    /// Starts a scoped edit, rolled back when the guard is dropped unless
+    /// committed.
+    pub fn edit(&mut self) -> ResettableGuard<'_, T>
+    where
+        T: Clone,
+    {
+        ResettableGuard::new(self)
+    }
+
+    /// Starts an edit that is normalized when the guard is dropped, so
+    /// reverting the value by hand makes the wrapper clean again.
+    pub fn edit_normalized(&mut self) -> ResettableNormalizingGuard<'_, T>
+    where
+        T: Clone + PartialEq,
+    {
+        ResettableNormalizingGuard::new(self)
+    }
+
+    /// Resets the wrapper only if `pred` holds for the current value.
+    #[must_use]
+    pub fn reset_if(self, pred: impl FnOnce(&T) -> bool) -> Self {
+        if pred(&self.inner) {
+            self.reset()
+        } else {
+            self
+        }
+    }
+
+    /// Commits a dirty wrapper and returns `(original, current)`, or `None`
+    /// if the wrapper is clean. The wrapper is clean afterwards either way.
+    pub fn take_dirty(&mut self) -> Option<(T, T)>
+    where
+        T: Clone,
+    {
+        let original = self.stash.clone()?;
+        self.commit_in_place();
+        Some((original, self.inner.clone()))
+    }
+
+    /// Replaces the value a reset would restore, leaving the current value
+    /// as is. The wrapper is dirty afterwards, even if `original` equals the
+    /// current value. The initial value is kept apart, so `reset_to_initial`
+    /// still restores the construction value.
+    pub fn set_original(&mut self, original: T)
+    where
+        T: Clone,
+    {
+        if let Some(old) = self.stash.replace(original) {
+            self.initial.get_or_insert(old);
+        } else if self.initial.is_none() {
+            self.initial = Some(self.inner.clone());
+        }
+    }
+
+    /// Forgets the restore point, same as `commit_in_place`.
+    pub fn clear_original(&mut self) {
+        self.commit_in_place();
+    }
+
+    /// Same as `reset`, but works through a mutable reference.
+    #[inline]
+    pub fn reset_in_place(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.inner = stash;
+        }
+    }
+
+    /// Resets in place and returns the discarded current value, or `None` if
+    /// the wrapper is clean.
+    pub fn reset_taking(&mut self) -> Option<T> {
+        let stash = self.stash.take()?;
+        Some(mem::replace(&mut self.inner, stash))
+    }
+
+    /// Consuming form of `reset_taking`: the reset wrapper and the discarded
+    /// current value, `None` if the wrapper was clean.
+    #[must_use]
+    pub fn reset_and_return(mut self) -> (Self, Option<T>) {
+        let discarded = self.reset_taking();
+        (self, discarded)
+    }
+
+    /// Resets in place and returns `true` if the wrapper was dirty, i.e. if
+    /// a value was restored.
+    pub fn reset_reporting(&mut self) -> bool {
+        let dirty = self.is_dirty();
+        self.reset_in_place();
+        dirty
+    }
+}
+
+impl<T> From<T> for ResettableWrapper<T> {
+    fn from(inner: T) -> Self {
+        ResettableWrapper::new(inner)
+    }
+}
+
+// Iterating by value consumes the current value, iterating by mutable
+// reference stashes first.
+
+impl<C> IntoIterator for ResettableWrapper<C>
+where
+    C: IntoIterator,
+{
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a ResettableWrapper<C>
+where
+    &'a C: IntoIterator,
+{
+    type Item = <&'a C as IntoIterator>::Item;
+    type IntoIter = <&'a C as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a mut ResettableWrapper<C>
+where
+    C: Clone,
+    &'a mut C: IntoIterator,
+{
+    type Item = <&'a mut C as IntoIterator>::Item;
+    type IntoIter = <&'a mut C as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().into_iter()
+    }
+}
+
+impl<C, A> Extend<A> for ResettableWrapper<C>
+where
+    C: Extend<A> + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A>,
+    {
+        self.deref_mut().extend(iter)
+    }
+}
+
+impl<C, A> FromIterator<A> for ResettableWrapper<C>
+where
+    C: FromIterator<A>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        ResettableWrapper::new(iter.into_iter().collect())
+    }
+}
+
+impl<T> Resettable for ResettableWrapper<T> {
+    // A clean wrapper is returned as is, without touching the value.
+    #[inline]
+    fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+}
+
+// Transparent, except that the alternate form `{:#?}` shows the tracking
+// state as well. The initial value is shown only if a commit set it apart.
+impl<T> fmt::Debug for ResettableWrapper<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return self.inner.fmt(f);
+        }
+        let mut s = f.debug_struct("ResettableWrapper");
+        s.field("inner", &self.inner).field("original", &self.stash);
+        if let Some(initial) = &self.initial {
+            s.field("initial", initial);
+        }
+        s.finish()
+    }
+}
+
+impl<T> fmt::Display for ResettableWrapper<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+// Comparison and hashing look at the current value only, the same value
+// `Deref`, `Debug` and serde expose.
+
+impl<T> PartialEq for ResettableWrapper<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T> Eq for ResettableWrapper<T> where T: Eq {}
+
+impl<T> PartialEq<T> for ResettableWrapper<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.inner == *other
+    }
+}
+
+// The coherence rules only allow the reverse comparison for concrete types.
+macro_rules! primitive_eq {
+    ($($ty:ty)*) => {
+        $(
+            impl PartialEq<ResettableWrapper<$ty>> for $ty {
+                fn eq(&self, other: &ResettableWrapper<$ty>) -> bool {
+                    *self == other.inner
+                }
+            }
+        )*
+    };
+}
+
+primitive_eq!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 bool char);
+
+// `impl<T: Sum> Sum<ResettableWrapper<T>> for T` is rejected by the coherence
+// rules, so the numeric types get their own impls over the current values.
+macro_rules! primitive_sum {
+    ($($ty:ty)*) => {
+        $(
+            impl Sum<ResettableWrapper<$ty>> for $ty {
+                fn sum<I: Iterator<Item = ResettableWrapper<$ty>>>(iter: I) -> Self {
+                    iter.map(ResettableWrapper::into_inner).sum()
+                }
+            }
+
+            impl<'a> Sum<&'a ResettableWrapper<$ty>> for $ty {
+                fn sum<I: Iterator<Item = &'a ResettableWrapper<$ty>>>(iter: I) -> Self {
+                    iter.map(|wrapper| wrapper.inner).sum()
+                }
+            }
+
+            impl Product<ResettableWrapper<$ty>> for $ty {
+                fn product<I: Iterator<Item = ResettableWrapper<$ty>>>(iter: I) -> Self {
+                    iter.map(ResettableWrapper::into_inner).product()
+                }
+            }
+
+            impl<'a> Product<&'a ResettableWrapper<$ty>> for $ty {
+                fn product<I: Iterator<Item = &'a ResettableWrapper<$ty>>>(iter: I) -> Self {
+                    iter.map(|wrapper| wrapper.inner).product()
+                }
+            }
+        )*
+    };
+}
+
+primitive_sum!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+/// Sums the current values into a clean wrapper.
+impl<T> Sum for ResettableWrapper<T>
+where
+    T: Sum,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        ResettableWrapper::new(iter.map(ResettableWrapper::into_inner).sum())
+    }
+}
+
+/// Multiplies the current values into a clean wrapper.
+impl<T> Product for ResettableWrapper<T>
+where
+    T: Product,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        ResettableWrapper::new(iter.map(ResettableWrapper::into_inner).product())
+    }
+}
+
+impl<T> PartialOrd for ResettableWrapper<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<T> Ord for ResettableWrapper<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<T> Hash for ResettableWrapper<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
+// A clean wrapper is an arbitrary `T`, the stash is an arbitrary `Option<T>`,
+// so clean and dirty wrappers are both generated depending on the input. The
+// initial value is another arbitrary `Option<T>`, with `None` meaning nothing
+// was committed.
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for ResettableWrapper<T>
+where
+    T: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(ResettableWrapper {
+            inner: u.arbitrary()?,
+            stash: u.arbitrary()?,
+            initial: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and_all(&[
+            T::size_hint(depth),
+            <Option<T> as arbitrary::Arbitrary>::size_hint(depth),
+            <Option<T> as arbitrary::Arbitrary>::size_hint(depth),
+        ])
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use self::serde_m::ResettableWithHistory;
+
+#[cfg(feature = "serde")]
+mod serde_m {
+    use core::{fmt, marker::PhantomData};
+
+    use serde::{ser, de};
+
+    use super::ResettableWrapper;
+
+    // The wrapper is invisible on the wire, it serializes exactly as the
+    // current value does. The stash is dropped, so a dirty wrapper comes back
+    // clean, see `ResettableWithHistory` to keep it.
+    impl<T> ser::Serialize for ResettableWrapper<T>
+    where
+        T: ser::Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            self.inner.serialize(serializer)
+        }
+    }
+
+    impl<'de, T> de::Deserialize<'de> for ResettableWrapper<T>
+    where
+        T: de::Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            T::deserialize(deserializer).map(ResettableWrapper::new)
+        }
+    }
+
+    /// Serializes the stash along with the value, so a dirty wrapper can still
+    /// be reset after a round trip. `ResettableWrapper` itself serializes only
+    /// the current value.
+    ///
+    /// The wire format is a struct with three fields, `inner: T`,
+    /// `original: Option<T>`, which is `None` for a clean wrapper, and
+    /// `initial: Option<T>`, which is `None` unless a commit set the initial
+    /// value apart. When deserializing, a missing `original` or `initial`
+    /// means `None`, so payloads without `initial` are still accepted by
+    /// self-describing formats.
+    #[derive(Default, Clone, Debug)]
+    pub struct ResettableWithHistory<T>(pub ResettableWrapper<T>);
+
+    impl<T> From<ResettableWrapper<T>> for ResettableWithHistory<T> {
+        fn from(wrapper: ResettableWrapper<T>) -> Self {
+            ResettableWithHistory(wrapper)
+        }
+    }
+
+    impl<T> From<ResettableWithHistory<T>> for ResettableWrapper<T> {
+        fn from(history: ResettableWithHistory<T>) -> Self {
+            history.0
+        }
+    }
+
+    const FIELDS: &[&str] = &["inner", "original", "initial"];
+
+    impl<T> ser::Serialize for ResettableWithHistory<T>
+    where
+        T: ser::Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            use serde::ser::SerializeStruct;
+
+            let mut s = serializer.serialize_struct("ResettableWithHistory", 3)?;
+            s.serialize_field("inner", &self.0.inner)?;
+            s.serialize_field("original", &self.0.stash)?;
+            s.serialize_field("initial", &self.0.initial)?;
+            s.end()
+        }
+    }
+
+    enum Field {
+        Inner,
+        Original,
+        Initial,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            struct FieldVisitor;
+
+            impl<'de> de::Visitor<'de> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("`inner`, `original` or `initial`")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "inner" => Ok(Field::Inner),
+                        "original" => Ok(Field::Original),
+                        "initial" => Ok(Field::Initial),
+                        _ => Err(de::Error::unknown_field(value, FIELDS)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct HistoryVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for HistoryVisitor<T>
+    where
+        T: de::Deserialize<'de>,
+    {
+        type Value = ResettableWithHistory<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("struct ResettableWithHistory")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let inner = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let stash = seq.next_element()?.flatten();
+            let initial = seq.next_element()?.flatten();
+            Ok(ResettableWithHistory(ResettableWrapper {
+                inner,
+                stash,
+                initial,
+            }))
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut inner = None;
+            let mut stash = None;
+            let mut initial = None;
+            while let Some(key) = map.next_key()? {
+                match key {
+                    Field::Inner => {
+                        if inner.is_some() {
+                            return Err(de::Error::duplicate_field("inner"));
+                        }
+                        inner = Some(map.next_value()?);
+                    },
+                    Field::Original => {
+                        if stash.is_some() {
+                            return Err(de::Error::duplicate_field("original"));
+                        }
+                        stash = Some(map.next_value()?);
+                    },
+                    Field::Initial => {
+                        if initial.is_some() {
+                            return Err(de::Error::duplicate_field("initial"));
+                        }
+                        initial = Some(map.next_value()?);
+                    },
+                }
+            }
+            let inner = inner.ok_or_else(|| de::Error::missing_field("inner"))?;
+            Ok(ResettableWithHistory(ResettableWrapper {
+                inner,
+                stash: stash.flatten(),
+                initial: initial.flatten(),
+            }))
+        }
+    }
+
+    impl<'de, T> de::Deserialize<'de> for ResettableWithHistory<T>
+    where
+        T: de::Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            let visitor = HistoryVisitor(PhantomData);
+            deserializer.deserialize_struct("ResettableWithHistory", FIELDS, visitor)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde::{Serialize, Deserialize};
+
+        use super::{ResettableWrapper, ResettableWithHistory};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Address {
+            city: ResettableWrapper<String>,
+            zip: u32,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Person {
+            name: ResettableWrapper<String>,
+            age: u32,
+            tags: ResettableWrapper<Vec<String>>,
+            #[serde(flatten)]
+            address: Address,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Plain {
+            name: String,
+            age: u32,
+            tags: Vec<String>,
+            city: String,
+            zip: u32,
+        }
+
+        #[test]
+        fn transparent() {
+            let mut person = Person {
+                name: ResettableWrapper::new("alice".to_string()),
+                age: 30,
+                tags: ResettableWrapper::new(vec![]),
+                address: Address {
+                    city: ResettableWrapper::new("paris".to_string()),
+                    zip: 75000,
+                },
+            };
+            person.tags.push("admin".to_string());
+
+            let json = serde_json::to_string(&person).unwrap();
+            assert_eq!(
+                json,
+                r#"{"name":"alice","age":30,"tags":["admin"],"city":"paris","zip":75000}"#,
+            );
+            let plain = serde_json::from_str::<Plain>(&json).unwrap();
+            assert_eq!(plain.tags, ["admin"]);
+            let back = serde_json::from_str::<Person>(&json).unwrap();
+            assert_eq!(back, person);
+            assert!(back.tags.is_clean());
+
+            let address = Address {
+                city: ResettableWrapper::new("paris".to_string()),
+                zip: 75000,
+            };
+            let bytes = bincode::serialize(&address).unwrap();
+            assert_eq!(bytes, bincode::serialize(&("paris", 75000u32)).unwrap());
+            assert_eq!(bincode::deserialize::<Address>(&bytes).unwrap(), address);
+        }
+
+        #[test]
+        fn history_round_trip() {
+            let clean = ResettableWithHistory(ResettableWrapper::new(1));
+            let json = serde_json::to_string(&clean).unwrap();
+            assert_eq!(json, r#"{"inner":1,"original":null,"initial":null}"#);
+            let clean = serde_json::from_str::<ResettableWithHistory<u32>>(&json).unwrap();
+            assert!(clean.0.is_clean());
+
+            let mut dirty = ResettableWrapper::new(1);
+            *dirty = 2;
+            let json = serde_json::to_string(&ResettableWithHistory(dirty)).unwrap();
+            assert_eq!(json, r#"{"inner":2,"original":1,"initial":null}"#);
+            let dirty = serde_json::from_str::<ResettableWithHistory<_>>(&json).unwrap();
+            let dirty = ResettableWrapper::<u32>::from(dirty);
+            assert_eq!(*dirty, 2);
+            assert_eq!(dirty.reset_inner(), 1);
+        }
+
+        #[test]
+        fn history_payloads() {
+            fn parse(json: &str) -> ResettableWrapper<u32> {
+                serde_json::from_str::<ResettableWithHistory<_>>(json)
+                    .unwrap()
+                    .into()
+            }
+
+            let clean = parse(r#"{"inner":1}"#);
+            assert!(clean.is_clean());
+            assert_eq!(clean, 1);
+
+            let dirty = parse(r#"{"original":1,"inner":2}"#);
+            assert_eq!(dirty.original(), Some(&1));
+            assert_eq!(dirty, 2);
+
+            let same = parse(r#"{"inner":1,"original":1}"#);
+            assert!(same.is_dirty());
+            assert_eq!(same.reset_inner(), 1);
+
+            let error = serde_json::from_str::<ResettableWithHistory<u32>>(r#"{"original":1}"#);
+            assert!(error.is_err());
+        }
+
+        #[test]
+        fn history_keeps_initial() {
+            let mut wrapper = ResettableWrapper::new(1);
+            *wrapper = 2;
+            wrapper.commit_in_place();
+            *wrapper = 3;
+
+            let json = serde_json::to_string(&ResettableWithHistory(wrapper.clone())).unwrap();
+            assert_eq!(json, r#"{"inner":3,"original":2,"initial":1}"#);
+            let back = serde_json::from_str::<ResettableWithHistory<u32>>(&json).unwrap();
+            assert_eq!(back.0.initial(), &1);
+            assert_eq!(back.0.reset_to_initial(), 1);
+
+            let bytes = bincode::serialize(&ResettableWithHistory(wrapper)).unwrap();
+            let back = bincode::deserialize::<ResettableWithHistory<u32>>(&bytes).unwrap();
+            assert_eq!(back.0.initial(), &1);
+            assert_eq!(back.0.reset_inner(), 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResettableWrapper, Resettable, ResettableSliceExt};
+
+    #[test]
+    fn basic() {
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        struct Complex {
+            first: ResettableWrapper<usize>,
+            second: ResettableWrapper<String>,
+        }
+
+        impl Resettable for Complex {
+            fn reset(self) -> Self {
+                Complex {
+                    first: self.first.reset(),
+                    second: self.second.reset(),
+                }
+            }
+        }
+
+        let original = Complex {
+            first: 123.into(),
+            second: "foo".to_string().into(),
+        };
+        let mut complex = original.clone();
+        println!("original: {:?}", complex);
+
+        *complex.first += 100;
+        println!("mutated first: {:?}", complex);
+
+        let mut complex = complex.reset();
+
+        *complex.second = "bar".to_string();
+        println!("mutated second: {:?}", complex);
+
+        let reset = complex.reset();
+        println!("original: {:?}", reset);
+
+        assert_eq!(reset, original);
+    }
+
+    #[test]
+    fn unwrap() {
+        fn convert<T: Into<ResettableWrapper<u32>>>(value: T) -> u32 {
+            value.into().into_inner()
+        }
+
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper = 2;
+        assert_eq!(convert(wrapper.clone()), 2);
+        assert_eq!(convert(3), 3);
+        assert_eq!(wrapper.reset_inner(), 1);
+    }
+
+    #[test]
+    fn new_dirty() {
+        let wrapper = ResettableWrapper::new_dirty(2, 1);
+        assert!(wrapper.is_dirty());
+        assert_eq!(*wrapper, 2);
+        assert_eq!(wrapper.reset(), ResettableWrapper::new(1));
+    }
+
+    #[test]
+    fn parts() {
+        let clean = ResettableWrapper::new(1);
+        assert_eq!(clean.clone().into_parts(), (1, None));
+        let (inner, stash) = clean.clone().into_parts();
+        assert_eq!(ResettableWrapper::from_parts(inner, stash), clean);
+
+        let dirty = ResettableWrapper::new_dirty(2, 1);
+        assert_eq!(dirty.clone().into_parts(), (2, Some(1)));
+        let (inner, stash) = dirty.into_parts();
+        let dirty = ResettableWrapper::from_parts(inner, stash);
+        assert_eq!(dirty, 2);
+        assert_eq!(dirty.reset_inner(), 1);
+    }
+
+    #[test]
+    fn dirty() {
+        let mut wrapper = ResettableWrapper::new(123);
+        assert!(wrapper.is_clean());
+
+        *wrapper += 0;
+        assert!(wrapper.is_dirty());
+
+        let wrapper = wrapper.reset();
+        assert!(!wrapper.is_dirty());
+    }
+
+    #[test]
+    fn original() {
+        let mut wrapper = ResettableWrapper::new(123);
+        assert_eq!(wrapper.original(), None);
+
+        *wrapper += 100;
+        assert_eq!(wrapper.original(), Some(&123));
+        assert_eq!(*wrapper, 223);
+    }
+
+    #[test]
+    fn rollback_value() {
+        let mut wrapper = ResettableWrapper::new(1);
+        assert_eq!(wrapper.rollback_value(), &1);
+        *wrapper = 2;
+        assert_eq!(wrapper.rollback_value(), &1);
+        assert_eq!(*wrapper, 2);
+    }
+
+    #[test]
+    fn inspect() {
+        let describe = |current: &u32, original: Option<&u32>| match original {
+            Some(original) => format!("changed from {} to {}", original, current),
+            None => format!("unchanged {}", current),
+        };
+
+        let mut wrapper = ResettableWrapper::new(1);
+        assert_eq!(wrapper.inspect(describe), "unchanged 1");
+        *wrapper = 2;
+        assert_eq!(wrapper.inspect(describe), "changed from 1 to 2");
+    }
+
+    #[test]
+    fn changed() {
+        let mut wrapper = ResettableWrapper::new(1);
+        assert!(!wrapper.changed());
+
+        *wrapper += 0;
+        assert!(wrapper.is_dirty());
+        assert!(!wrapper.changed());
+        assert_eq!(wrapper.diff(), None);
+
+        *wrapper += 1;
+        assert!(wrapper.changed());
+        assert_eq!(wrapper.diff(), Some(&1));
+    }
+
+    #[test]
+    fn commit() {
+        let mut wrapper = ResettableWrapper::new(123);
+        *wrapper += 100;
+
+        let mut wrapper = wrapper.commit();
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, 223);
+
+        *wrapper += 100;
+        assert_eq!(wrapper.reset_inner(), 223);
+
+        let clean = ResettableWrapper::new(1).commit();
+        assert!(clean.is_clean());
+        assert_eq!(clean.into_inner(), 1);
+    }
+
+    #[test]
+    fn modify() {
+        let mut wrapper = ResettableWrapper::new(vec![1, 2]);
+        let len = wrapper.modify(|v| {
+            v.push(3);
+            v.len()
+        });
+        assert_eq!(len, 3);
+        assert_eq!(wrapper.original(), Some(&vec![1, 2]));
+        assert_eq!(wrapper.reset_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn replace() {
+        let mut wrapper = ResettableWrapper::new("foo".to_string());
+        assert_eq!(wrapper.replace("bar".to_string()), "foo");
+        assert_eq!(wrapper.replace("baz".to_string()), "bar");
+        assert_eq!(*wrapper, "baz");
+        assert_eq!(wrapper.reset_inner(), "foo");
+    }
+
+    #[test]
+    fn flatten() {
+        let mut nested = ResettableWrapper::new(ResettableWrapper::new(1));
+        assert!(nested.clone().flatten().is_clean());
+
+        **nested = 2;
+        let flat = nested.clone().flatten();
+        assert_eq!(*flat, 2);
+        assert_eq!(flat.reset_inner(), 1);
+
+        let mut inner = ResettableWrapper::new(1);
+        *inner = 2;
+        let mut nested = ResettableWrapper::new(inner);
+        assert_eq!(nested.clone().flatten().reset_inner(), 1);
+
+        **nested = 3;
+        assert_eq!(*nested.clone().reset().into_inner(), 2);
+        let flat = nested.flatten();
+        assert_eq!(*flat, 3);
+        assert_eq!(flat.reset_inner(), 1);
+    }
+
+    #[test]
+    fn take() {
+        let mut wrapper = ResettableWrapper::new(vec![1, 2]);
+        let mut taken = wrapper.take();
+        assert!(wrapper.is_empty());
+
+        taken.push(3);
+        *wrapper = taken;
+        assert_eq!(*wrapper, [1, 2, 3]);
+        assert_eq!(wrapper.reset_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn clone_clean() {
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper = 2;
+
+        let clone = wrapper.clone();
+        assert!(clone.is_dirty());
+        assert_eq!(clone.reset_inner(), 1);
+
+        let clean = wrapper.clone_clean();
+        assert!(clean.is_clean());
+        assert_eq!(clean, wrapper);
+        assert_eq!(clean.reset_inner(), 2);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper = 2;
+        let snap = wrapper.snapshot();
+        *wrapper = 3;
+
+        wrapper.restore(snap);
+        assert_eq!(*wrapper, 2);
+        assert_eq!(wrapper.original(), Some(&1));
+
+        let mut wrapper = ResettableWrapper::new(1);
+        let snap = wrapper.snapshot();
+        wrapper.restore(snap);
+        assert_eq!(wrapper.reset_inner(), 1);
+    }
+
+    #[test]
+    fn reset_to() {
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper = 2;
+        let wrapper = wrapper.reset_to(3);
+        assert!(wrapper.is_clean());
+        assert_eq!(wrapper, 3);
+    }
+
+    #[test]
+    fn reset_if() {
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper = -1;
+        let wrapper = wrapper.reset_if(|v| *v > 0);
+        assert_eq!(*wrapper, -1);
+        assert!(wrapper.is_dirty());
+
+        let wrapper = wrapper.reset_if(|v| *v < 0);
+        assert_eq!(*wrapper, 1);
+        assert!(wrapper.is_clean());
+
+        let wrapper = wrapper.reset_if(|_| true);
+        assert_eq!(*wrapper, 1);
+    }
+
+    #[test]
+    fn map() {
+        let mut wrapper = ResettableWrapper::new("1".to_string());
+        *wrapper = "2".to_string();
+
+        let mapped = wrapper.map(|s| s.parse::<u32>().unwrap());
+        assert_eq!(*mapped, 2);
+        assert!(mapped.is_dirty());
+        assert_eq!(*mapped.reset(), 1);
+
+        let clean = ResettableWrapper::new(1).map(|v| v * 10);
+        assert!(clean.is_clean());
+        assert_eq!(*clean, 10);
+    }
+
+    #[test]
+    fn as_ref() {
+        fn len(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+
+        fn zero(mut bytes: impl AsMut<[u8]>) {
+            bytes.as_mut().iter_mut().for_each(|b| *b = 0);
+        }
+
+        let wrapper = ResettableWrapper::new("foo".to_string());
+        assert_eq!(len(&wrapper), 3);
+        assert!(wrapper.is_clean());
+
+        let mut wrapper = ResettableWrapper::new(vec![1u8, 2]);
+        zero(&mut wrapper);
+        assert_eq!(*wrapper, [0, 0]);
+        assert_eq!(wrapper.reset_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn display() {
+        let mut number = ResettableWrapper::new(1);
+        *number += 122;
+        assert_eq!(format!("{}", number), "123");
+        assert_eq!(format!("{:>5}", number), "  123");
+        assert_eq!(ResettableWrapper::new("foo").to_string(), "foo");
+    }
+
+    #[test]
+    fn compare_inner_only() {
+        let mut stale = ResettableWrapper::new(1);
+        *stale = 2;
+        *stale = 1;
+        assert!(stale.is_dirty());
+        assert_eq!(stale, ResettableWrapper::new(1));
+        assert_eq!(
+            stale.cmp(&ResettableWrapper::new(1)),
+            std::cmp::Ordering::Equal
+        );
+        assert!(stale < ResettableWrapper::new(2));
+    }
+
+    #[test]
+    fn compare_bare() {
+        let mut wrapper = ResettableWrapper::new(123u32);
+        assert_eq!(wrapper, 123);
+        assert_eq!(123, wrapper);
+
+        *wrapper += 1;
+        assert_eq!(wrapper, 124);
+        assert_ne!(wrapper, 123);
+        assert_eq!(124, wrapper);
+
+        assert_eq!(ResettableWrapper::new("foo"), "foo");
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut u = Unstructured::new(&[1, 0, 0, 0, 1, 2, 0, 0, 0]);
+        let dirty = ResettableWrapper::<u32>::arbitrary(&mut u).unwrap();
+        assert_eq!(*dirty, 1);
+        assert_eq!(dirty.original(), Some(&2));
+
+        let mut u = Unstructured::new(&[1, 0, 0, 0, 0]);
+        let clean = ResettableWrapper::<u32>::arbitrary(&mut u).unwrap();
+        assert!(clean.is_clean());
+    }
+
+    #[test]
+    fn borrow() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let mut key = ResettableWrapper::new(1u32);
+        *key = 2;
+
+        let mut map = HashMap::new();
+        map.insert(key.clone(), "foo");
+        assert_eq!(map.get(&2), Some(&"foo"));
+        assert_eq!(map.get(&1), None);
+
+        let set = [key].into_iter().collect::<BTreeSet<_>>();
+        assert!(set.contains(&2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cow() {
+        use std::borrow::Cow;
+
+        static DEFAULT: &str = "localhost";
+
+        let mut host = ResettableWrapper::<Cow<'static, str>>::from_default(DEFAULT);
+        assert!(host.is_borrowed());
+
+        host.to_mut().push_str(":8080");
+        assert!(!host.is_borrowed());
+        assert_eq!(*host, "localhost:8080");
+
+        let host = host.reset();
+        assert!(host.is_borrowed());
+        assert_eq!(*host, DEFAULT);
+    }
+
+    #[test]
+    fn hash() {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashSet},
+            hash::{Hash, Hasher},
+        };
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut dirty = ResettableWrapper::new(1);
+        *dirty = 2;
+        assert_eq!(hash_of(&dirty), hash_of(&ResettableWrapper::new(2)));
+        assert_eq!(hash_of(&dirty), hash_of(&2));
+
+        let mut set = HashSet::new();
+        set.insert(ResettableWrapper::new("foo"));
+        set.insert(ResettableWrapper::new("bar"));
+        set.insert(ResettableWrapper::new("foo"));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&ResettableWrapper::new("bar")));
+    }
+
+    #[test]
+    fn take_dirty() {
+        let mut wrapper = ResettableWrapper::new(1);
+        assert_eq!(wrapper.take_dirty(), None);
+
+        *wrapper = 2;
+        assert_eq!(wrapper.take_dirty(), Some((1, 2)));
+        assert!(wrapper.is_clean());
+        assert_eq!(wrapper.take_dirty(), None);
+        assert_eq!(wrapper.reset_inner(), 2);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut wrapper = ResettableWrapper::new(vec![1u32, 2, 3]);
+
+        let mut sum = 0;
+        for x in &wrapper {
+            sum += x;
+        }
+        assert_eq!(sum, 6);
+        assert!(wrapper.is_clean());
+
+        for x in &mut wrapper {
+            *x *= 10;
+        }
+        assert_eq!(wrapper.original(), Some(&vec![1, 2, 3]));
+        assert_eq!(wrapper.into_iter().collect::<Vec<_>>(), [10, 20, 30]);
+    }
+
+    #[test]
+    fn reset_all() {
+        let mut items = [1, 2, 3].map(ResettableWrapper::new);
+        *items[0] = 10;
+        *items[2] = 30;
+
+        items.reset_all();
+        assert!(items.iter().all(ResettableWrapper::is_clean));
+        assert_eq!(items, [1, 2, 3].map(ResettableWrapper::new));
+    }
+
+    #[test]
+    fn set_original() {
+        let mut wrapper = ResettableWrapper::new(1);
+        wrapper.set_original(1);
+        assert!(wrapper.is_dirty());
+
+        *wrapper = 2;
+        wrapper.set_original(3);
+        assert_eq!(*wrapper, 2);
+        assert_eq!(wrapper.original(), Some(&3));
+
+        wrapper.clear_original();
+        assert!(wrapper.is_clean());
+        assert_eq!(wrapper.reset_inner(), 2);
+    }
+
+    #[test]
+    fn extend() {
+        let mut wrapper = (1..3).collect::<ResettableWrapper<Vec<u32>>>();
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, [1, 2]);
+
+        wrapper.extend([3, 4]);
+        assert!(wrapper.is_dirty());
+        assert_eq!(*wrapper, [1, 2, 3, 4]);
+        assert_eq!(wrapper.reset_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn reset_in_place() {
+        struct Container {
+            field: ResettableWrapper<String>,
+        }
+
+        let mut container = Container {
+            field: "foo".to_string().into(),
+        };
+        container.field.reset_in_place();
+        assert_eq!(*container.field, "foo");
+
+        container.field.push_str("bar");
+        let field = &mut container.field;
+        field.reset_in_place();
+        assert!(field.is_clean());
+        assert_eq!(container.field.into_inner(), "foo");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reset_mut() {
+        use super::ResettableMut;
+
+        fn clear<T: ResettableMut>(mut value: T) {
+            value.reset_mut();
+        }
+
+        let mut wrappers = vec![ResettableWrapper::new(1), ResettableWrapper::new(2)];
+        *wrappers[1] = 3;
+        let first = &mut wrappers[0];
+        *first = ResettableWrapper::new_dirty(4, 1);
+        clear(&mut wrappers);
+        assert_eq!(wrappers, [1, 2]);
+
+        let mut wrapper = ResettableWrapper::new(Some(1));
+        *wrapper = None;
+        clear(&mut &mut wrapper);
+        assert_eq!(wrapper.into_inner(), Some(1));
+    }
+
+    #[test]
+    fn normalize() {
+        let mut wrapper = ResettableWrapper::new("foo".to_string());
+        wrapper.normalize();
+        assert!(wrapper.is_clean());
+
+        wrapper.push_str("bar");
+        wrapper.normalize();
+        assert!(wrapper.is_dirty());
+
+        wrapper.truncate(3);
+        assert!(wrapper.is_dirty());
+        wrapper.normalize();
+        assert!(wrapper.is_clean());
+        assert_eq!(wrapper.reset_inner(), "foo");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reset_preserving_capacity() {
+        let mut wrapper = ResettableWrapper::new(vec![1, 2]);
+        wrapper.reserve(64);
+        let capacity = wrapper.capacity();
+        wrapper.reset_preserving_capacity();
+        assert_eq!(wrapper.capacity(), capacity);
+
+        wrapper.push(3);
+        wrapper.reset_preserving_capacity();
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, [1, 2]);
+        assert_eq!(wrapper.capacity(), capacity);
+
+        let mut wrapper = ResettableWrapper::new("foo".to_string());
+        wrapper.push_str(&"bar".repeat(16));
+        let capacity = wrapper.capacity();
+        wrapper.reset_preserving_capacity();
+        assert_eq!(*wrapper, "foo");
+        assert_eq!(wrapper.capacity(), capacity);
+    }
+
+    #[test]
+    fn duration_saturating_add() {
+        use core::time::Duration;
+
+        let mut timeout = ResettableWrapper::new(Duration::from_secs(1));
+        timeout.saturating_add(Duration::from_secs(2));
+        assert_eq!(*timeout, Duration::from_secs(3));
+
+        timeout.saturating_add(Duration::MAX);
+        assert_eq!(*timeout, Duration::MAX);
+        assert_eq!(timeout.reset_inner(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn reset_to_initial() {
+        let mut wrapper = ResettableWrapper::new("a".to_string());
+        assert_eq!(wrapper.initial(), "a");
+
+        *wrapper = "b".to_string();
+        assert_eq!(wrapper.initial(), "a");
+        let mut wrapper = wrapper.commit();
+        assert!(wrapper.is_clean());
+        assert_eq!(wrapper.initial(), "a");
+
+        *wrapper = "c".to_string();
+        let wrapper = wrapper.reset();
+        assert_eq!(*wrapper, "b");
+        assert_eq!(wrapper.initial(), "a");
+
+        let mut wrapper = wrapper.reset_to_initial();
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, "a");
+
+        // Later commits don't move the initial baseline.
+        *wrapper = "d".to_string();
+        wrapper.commit_in_place();
+        *wrapper = "e".to_string();
+        wrapper.commit_in_place();
+        assert_eq!(wrapper.clone().reset_inner(), "e");
+        assert_eq!(wrapper.reset_to_initial().into_inner(), "a");
+    }
+
+    #[test]
+    fn from_const() {
+        static LIMIT: ResettableWrapper<u32> = ResettableWrapper::from_const(8);
+        const NAME: ResettableWrapper<&str> = ResettableWrapper::from_const("foo");
+
+        assert!(LIMIT.is_clean());
+        assert_eq!(*LIMIT, 8);
+
+        let mut name = NAME;
+        *name = "bar";
+        assert_eq!(name.reset_inner(), "foo");
+    }
+
+    #[test]
+    fn replace_with() {
+        let mut wrapper = ResettableWrapper::new(1);
+        wrapper.replace_with(|old| old + 1);
+        wrapper.replace_with(|old| old * 10);
+        assert_eq!(*wrapper, 20);
+        assert_eq!(wrapper.reset_inner(), 1);
+
+        let mut wrapper = ResettableWrapper::new_dirty(2, 1);
+        let panicking = std::panic::AssertUnwindSafe(|| wrapper.replace_with(|_| panic!()));
+        assert!(std::panic::catch_unwind(panicking).is_err());
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, 1);
+    }
+
+    #[test]
+    fn as_deref() {
+        let wrapper = ResettableWrapper::new("foo".to_string());
+        let name: &str = wrapper.as_deref();
+        assert_eq!(name, "foo");
+
+        let wrapper = ResettableWrapper::new(vec![1, 2]);
+        assert_eq!(wrapper.as_deref(), [1, 2]);
+        assert!(wrapper.is_clean());
+    }
+
+    #[test]
+    fn non_clone() {
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+
+        // Only mutable access needs `Clone`, everything else works without.
+        let wrapper = ResettableWrapper::new(Handle(1));
+        assert_eq!(wrapper.0, 1);
+        assert!(wrapper.is_clean());
+        let wrapper = wrapper.reset().commit();
+        assert_eq!(wrapper.into_inner(), Handle(1));
+
+        let wrapper = ResettableWrapper::new_dirty(Handle(2), Handle(1));
+        let (current, original) = wrapper.into_parts();
+        assert_eq!(current, Handle(2));
+        let wrapper = ResettableWrapper::from_parts(current, original);
+        assert_eq!(wrapper.reset_inner(), Handle(1));
+    }
+
+    #[test]
+    fn zip() {
+        let clean = || ResettableWrapper::new(1);
+        let dirty = || ResettableWrapper::new_dirty('b', 'a');
+
+        let both = clean().zip(ResettableWrapper::new('a'));
+        assert!(both.is_clean());
+        assert_eq!(both.reset_inner(), (1, 'a'));
+
+        let left = ResettableWrapper::new_dirty(2, 1).zip(ResettableWrapper::new('a'));
+        assert!(left.is_dirty());
+        assert_eq!(*left, (2, 'a'));
+        assert_eq!(left.reset_inner(), (1, 'a'));
+
+        let right = clean().zip(dirty());
+        assert!(right.is_dirty());
+        assert_eq!(right.reset_inner(), (1, 'a'));
+
+        let both = ResettableWrapper::new_dirty(2, 1).zip(dirty());
+        assert_eq!(*both, (2, 'b'));
+        assert_eq!(both.reset_inner(), (1, 'a'));
+    }
+
+    #[test]
+    fn reset_reporting() {
+        let mut fields = [
+            ResettableWrapper::new(1),
+            ResettableWrapper::new_dirty(3, 2),
+        ];
+        let restored = fields.iter_mut().map(ResettableWrapper::reset_reporting);
+        let restored = restored.filter(|&restored| restored).count();
+        assert_eq!(restored, 1);
+        assert_eq!(fields, [1, 2]);
+        assert!(!fields[1].reset_reporting());
+    }
+
+    #[test]
+    fn idempotent() {
+        let clean = ResettableWrapper::new(vec![1]);
+        let dirty = ResettableWrapper::new_dirty(vec![2], vec![1]);
+        let mut reverted = clean.clone();
+        reverted.push(2);
+        reverted.pop();
+
+        for wrapper in [clean, dirty, reverted] {
+            let once = wrapper.clone().reset();
+            let twice = wrapper.reset().reset();
+            assert!(once.is_clean() && twice.is_clean());
+            assert_eq!(once, twice);
+            assert_eq!(once.into_parts(), twice.into_parts());
+        }
+    }
+
+    #[test]
+    fn get_mut_untracked() {
+        let mut wrapper = ResettableWrapper::new(1);
+        *wrapper.get_mut_untracked() = 2;
+        assert!(wrapper.is_clean());
+        wrapper.reset_in_place();
+        assert_eq!(*wrapper, 2);
+
+        *wrapper = 3;
+        *wrapper.get_mut_untracked() = 4;
+        assert!(wrapper.is_dirty());
+        assert_eq!(wrapper.reset_inner(), 2);
+    }
+
+    #[test]
+    fn read_only_forwarding() {
+        use std::collections::HashMap;
+
+        let list = ResettableWrapper::new(vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+        assert!(list.contains(&2));
+        assert_eq!(list.iter().sum::<i32>(), 6);
+
+        let text = ResettableWrapper::new("foo".to_string());
+        assert!(text.contains("oo") && text.starts_with('f'));
+
+        let map = ResettableWrapper::new(HashMap::from([(1, "a")]));
+        assert!(map.contains_key(&1));
+        assert_eq!(map.get(&1), Some(&"a"));
+
+        assert!(list.is_clean() && text.is_clean() && map.is_clean());
+    }
+
+    #[test]
+    fn reset_taking() {
+        let mut wrapper = ResettableWrapper::new("foo".to_string());
+        assert_eq!(wrapper.reset_taking(), None);
+
+        wrapper.push_str("bar");
+        assert_eq!(wrapper.reset_taking().as_deref(), Some("foobar"));
+        assert!(wrapper.is_clean());
+        assert_eq!(*wrapper, "foo");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reset_matching() {
+        let mut wrapper = ResettableWrapper::new(vec![1, 2, 3, 4]);
+        wrapper.reset_matching(|_| true);
+        assert!(wrapper.is_clean());
+
+        wrapper.iter_mut().for_each(|x| *x *= 10);
+        wrapper.reset_matching(|&x| x > 20);
+        assert_eq!(*wrapper, [10, 20, 3, 4]);
+
+        let mut wrapper = ResettableWrapper::new(vec![1, 2]);
+        wrapper[0] = 10;
+        wrapper.push(30);
+        wrapper.reset_matching(|_| true);
+        assert_eq!(*wrapper, [1, 2, 30]);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let mut values = vec![ResettableWrapper::new(1u32), 2.into(), 3.into()];
+        *values[2] = 4;
+        assert_eq!(values.iter().sum::<u32>(), 7);
+        assert_eq!(values.iter().product::<u32>(), 8);
+
+        let total = values.clone().into_iter().sum::<ResettableWrapper<u32>>();
+        assert!(total.is_clean());
+        assert_eq!(total, 7);
+        assert_eq!(values.into_iter().product::<u32>(), 8);
+    }
+
+    #[test]
+    fn debug() {
+        let mut wrapper = ResettableWrapper::new(1);
+        assert_eq!(format!("{:?}", wrapper), "1");
+        assert_eq!(
+            format!("{:#?}", wrapper),
+            "ResettableWrapper {\n    inner: 1,\n    original: None,\n}",
+        );
+
+        *wrapper = 2;
+        assert_eq!(format!("{:?}", wrapper), "2");
+        assert!(format!("{:#?}", wrapper).contains("original: Some(\n        1,\n    ),"));
+    }
+
+    #[test]
+    fn reset_all_macro() {
+        struct Form {
+            name: ResettableWrapper<String>,
+            age: ResettableWrapper<u32>,
+        }
+
+        impl Form {
+            fn discard(&mut self) {
+                reset_all!(self.name, self.age,);
+            }
+        }
+
+        reset_all!();
+
+        let mut count = ResettableWrapper::new(1);
+        *count = 2;
+        reset_all!(count);
+        assert!(count.is_clean());
+
+        let mut form = Form {
+            name: "foo".to_string().into(),
+            age: 1.into(),
+        };
+        form.name.push_str("bar");
+        *form.age = 2;
+        *count = 3;
+        form.discard();
+        reset_all!(count, form.name);
+        assert_eq!((*form.name).as_str(), "foo");
+        assert_eq!((*form.age, *count), (1, 1));
+    }
+
+    #[test]
+    fn reset_and_return() {
+        let (wrapper, discarded) = ResettableWrapper::new_dirty(2, 1).reset_and_return();
+        assert!(wrapper.is_clean());
+        assert_eq!((*wrapper, discarded), (1, Some(2)));
+
+        let (wrapper, discarded) = wrapper.reset_and_return();
+        assert!(wrapper.is_clean());
+        assert_eq!((*wrapper, discarded), (1, None));
+    }
+
+    #[test]
+    fn has_reverted() {
+        let mut wrapper = ResettableWrapper::new(1);
+        assert!(!wrapper.has_reverted());
+
+        *wrapper = 2;
+        assert!(wrapper.is_dirty() && wrapper.changed());
+        assert!(!wrapper.has_reverted());
+
+        *wrapper = 1;
+        assert!(wrapper.is_dirty() && !wrapper.changed());
+        assert!(wrapper.has_reverted());
+    }
+
+    #[test]
+    fn swap() {
+        let mut first = ResettableWrapper::new_dirty(2, 1);
+        let mut second = ResettableWrapper::new(3);
+        first.swap(&mut second);
+        assert!(first.is_clean() && second.is_dirty());
+        assert_eq!((*first, *second), (3, 2));
+        assert_eq!(second.clone().reset_inner(), 1);
+
+        first.swap_values(&mut second);
+        assert!(first.is_dirty() && second.is_dirty());
+        assert_eq!((*first, *second), (2, 3));
+        assert_eq!((first.reset_inner(), second.reset_inner()), (3, 1));
+    }
+
+    #[test]
+    fn fork() {
+        let mut trunk = ResettableWrapper::new(vec![1]);
+        trunk.push(2);
+
+        let mut branch = trunk.fork();
+        assert!(branch.is_clean());
+        branch.push(3);
+        trunk.push(4);
+        assert_eq!(*branch.clone().reset(), [1, 2]);
+        assert_eq!(*branch.clone().reset_to_initial(), [1]);
+        assert_eq!(*branch.clone_clean().reset_to_initial(), [1, 2, 3]);
+        assert_eq!(trunk.reset_inner(), [1]);
+    }
+
+    #[test]
+    fn set_original_keeps_initial() {
+        let mut wrapper = ResettableWrapper::new("a");
+        *wrapper = "b";
+        wrapper.set_original("x");
+        assert_eq!(wrapper.initial(), &"a");
+        wrapper.clear_original();
+        assert_eq!(wrapper.reset_to_initial().into_inner(), "a");
+
+        let mut wrapper = ResettableWrapper::new("a");
+        wrapper.set_original("x");
+        assert_eq!(wrapper.clone().reset_inner(), "x");
+        assert_eq!(wrapper.reset_to_initial().into_inner(), "a");
     }
 }
@@ -0,0 +1,185 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    fmt,
+};
+
+use super::Resettable;
+
+/// How `ResettableWrapperWith` saves a value on the first mutation and gets
+/// it back on reset.
+pub trait StashPolicy<T> {
+    type Stashed;
+
+    fn stash(value: &T) -> Self::Stashed;
+
+    fn restore(stashed: Self::Stashed) -> T;
+}
+
+/// Stashes a clone, the policy of `ResettableWrapper`.
+pub struct CloneStash;
+
+impl<T> StashPolicy<T> for CloneStash
+where
+    T: Clone,
+{
+    type Stashed = T;
+
+    fn stash(value: &T) -> Self::Stashed {
+        value.clone()
+    }
+
+    fn restore(stashed: Self::Stashed) -> T {
+        stashed
+    }
+}
+
+/// Stashes nothing and restores `T::default()`, for values that always
+/// reset to a blank state. Works for types that are not `Clone`.
+pub struct DefaultStash;
+
+impl<T> StashPolicy<T> for DefaultStash
+where
+    T: Default,
+{
+    type Stashed = ();
+
+    fn stash(_: &T) -> Self::Stashed {}
+
+    fn restore((): Self::Stashed) -> T {
+        T::default()
+    }
+}
+
+/// A `ResettableWrapper` with a pluggable stashing policy.
+pub struct ResettableWrapperWith<T, P = CloneStash>
+where
+    P: StashPolicy<T>,
+{
+    inner: T,
+    stash: Option<P::Stashed>,
+    phantom: PhantomData<P>,
+}
+
+impl<T, P> ResettableWrapperWith<T, P>
+where
+    P: StashPolicy<T>,
+{
+    pub fn new(inner: T) -> Self {
+        ResettableWrapperWith {
+            inner,
+            stash: None,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn reset_inner(self) -> T {
+        match self.stash {
+            Some(stash) => P::restore(stash),
+            None => self.inner,
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.stash.is_some()
+    }
+
+    pub fn reset_in_place(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.inner = P::restore(stash);
+        }
+    }
+
+    pub fn commit_in_place(&mut self) {
+        self.stash = None;
+    }
+}
+
+impl<T, P> From<T> for ResettableWrapperWith<T, P>
+where
+    P: StashPolicy<T>,
+{
+    fn from(inner: T) -> Self {
+        ResettableWrapperWith::new(inner)
+    }
+}
+
+impl<T, P> Deref for ResettableWrapperWith<T, P>
+where
+    P: StashPolicy<T>,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T, P> DerefMut for ResettableWrapperWith<T, P>
+where
+    P: StashPolicy<T>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.stash.is_none() {
+            self.stash = Some(P::stash(&self.inner));
+        }
+
+        &mut self.inner
+    }
+}
+
+impl<T, P> Resettable for ResettableWrapperWith<T, P>
+where
+    P: StashPolicy<T>,
+{
+    fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+}
+
+impl<T, P> fmt::Debug for ResettableWrapperWith<T, P>
+where
+    T: fmt::Debug,
+    P: StashPolicy<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultStash, Resettable, ResettableWrapperWith};
+
+    #[test]
+    fn clone_stash() {
+        let mut wrapper = ResettableWrapperWith::<_>::new(vec![1]);
+        wrapper.push(2);
+        assert!(wrapper.is_dirty());
+        assert_eq!(*wrapper.reset(), [1]);
+    }
+
+    #[test]
+    fn default_stash() {
+        #[derive(Default, Debug, PartialEq)]
+        struct Buffer(Vec<u8>);
+
+        let mut wrapper = ResettableWrapperWith::<_, DefaultStash>::new(Buffer(vec![1]));
+        wrapper.0.push(2);
+        wrapper.commit_in_place();
+        assert_eq!(wrapper.0, [1, 2]);
+
+        wrapper.0.push(3);
+        assert_eq!(wrapper.reset_inner(), Buffer::default());
+    }
+}
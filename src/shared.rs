@@ -0,0 +1,151 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    ops::{Deref, DerefMut},
+    fmt,
+};
+use alloc::rc::Rc;
+
+use super::Resettable;
+
+/// Like `ResettableWrapper`, but the values live behind `Rc`.
+///
+/// The first mutation still clones the value, but the original isn't copied
+/// into the stash, it is shared. Cloning the wrapper shares both the current
+/// and the original value, so many dirty copies of one big value cost one
+/// allocation each only once they are mutated, and `reset` never clones.
+#[derive(Default, Clone)]
+pub struct ResettableShared<T> {
+    inner: Rc<T>,
+    stash: Option<Rc<T>>,
+}
+
+impl<T> Deref for ResettableShared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for ResettableShared<T>
+where
+    T: Clone,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.stash.is_none() {
+            self.stash = Some(self.inner.clone());
+        }
+
+        Rc::make_mut(&mut self.inner)
+    }
+}
+
+impl<T> ResettableShared<T> {
+    pub fn new(inner: T) -> Self {
+        ResettableShared::new_shared(Rc::new(inner))
+    }
+
+    /// Creates a clean wrapper sharing `inner` with other owners.
+    pub fn new_shared(inner: Rc<T>) -> Self {
+        ResettableShared { inner, stash: None }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.stash.is_some()
+    }
+
+    pub fn into_shared(self) -> Rc<T> {
+        self.inner
+    }
+
+    /// Unwraps the current value if no other wrapper shares it, otherwise
+    /// gives the wrapper back untouched, like `Rc::try_unwrap`.
+    pub fn try_into_inner(self) -> Result<T, Self> {
+        let stash = self.stash;
+        Rc::try_unwrap(self.inner).map_err(|inner| ResettableShared { inner, stash })
+    }
+
+    /// Unwraps the value a reset would yield. Clones it if it's still shared.
+    pub fn reset_inner(self) -> T
+    where
+        T: Clone,
+    {
+        let rc = self.stash.unwrap_or(self.inner);
+        Rc::try_unwrap(rc).unwrap_or_else(|rc| (*rc).clone())
+    }
+}
+
+impl<T> From<T> for ResettableShared<T> {
+    fn from(inner: T) -> Self {
+        ResettableShared::new(inner)
+    }
+}
+
+impl<T> Resettable for ResettableShared<T> {
+    fn reset(mut self) -> Self {
+        if let Some(stash) = self.stash.take() {
+            self.inner = stash;
+        }
+        self
+    }
+}
+
+impl<T> fmt::Debug for ResettableShared<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{ResettableShared, Resettable};
+
+    #[test]
+    fn shared_original() {
+        let original = Rc::new(vec![0u8; 16]);
+        let mut copies = (0..4)
+            .map(|_| ResettableShared::new_shared(original.clone()))
+            .collect::<Vec<_>>();
+        copies[0].push(1);
+        assert!(copies[0].is_dirty());
+        assert_eq!(copies[1].len(), 16);
+        assert_eq!(Rc::strong_count(&original), 5);
+
+        let reset = copies.swap_remove(0).reset();
+        assert!(!reset.is_dirty());
+        assert!(Rc::ptr_eq(&reset.into_shared(), &original));
+    }
+
+    #[test]
+    fn reset_inner() {
+        let mut value = ResettableShared::new(1);
+        *value += 1;
+        let copy = value.clone();
+        assert_eq!(*copy, 2);
+        assert_eq!(value.reset_inner(), 1);
+        assert_eq!(copy.reset_inner(), 1);
+    }
+
+    #[test]
+    fn try_into_inner() {
+        let value = ResettableShared::new(vec![1]);
+        let copy = value.clone();
+        let value = value.try_into_inner().unwrap_err();
+        assert_eq!(*value, [1]);
+
+        drop(copy);
+        assert_eq!(value.try_into_inner().unwrap(), [1]);
+
+        let mut value = ResettableShared::new(vec![1]);
+        value.push(2);
+        let _copy = value.clone().reset();
+        assert_eq!(value.try_into_inner().unwrap(), [1, 2]);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    ops::{Deref, DerefMut},
+    fmt,
+};
+use alloc::vec::Vec;
+
+use super::Resettable;
+
+/// Like `ResettableWrapper`, but remembers a restore point per undo step.
+///
+/// Without `checkpoint` it behaves exactly like `ResettableWrapper`: the
+/// first mutation records the original value and `reset` returns to it.
+#[derive(Default, Clone)]
+pub struct ResettableStack<T> {
+    inner: T,
+    stash: Vec<T>,
+    recorded: bool,
+}
+
+impl<T> Deref for ResettableStack<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for ResettableStack<T>
+where
+    T: Clone,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if !self.recorded {
+            self.stash.push(self.inner.clone());
+            self.recorded = true;
+        }
+
+        &mut self.inner
+    }
+}
+
+impl<T> ResettableStack<T> {
+    pub fn new(inner: T) -> Self {
+        ResettableStack {
+            inner,
+            stash: Vec::new(),
+            recorded: false,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the value the wrapper was created with, skipping every
+    /// intermediate restore point.
+    pub fn reset_inner(self) -> T {
+        self.stash.into_iter().next().unwrap_or(self.inner)
+    }
+
+    /// Starts a new undo step. The next mutation records the current value
+    /// as a restore point.
+    pub fn checkpoint(&mut self) {
+        self.recorded = false;
+    }
+
+    /// Returns to the most recent restore point. Returns `false` if there
+    /// is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(stash) = self.stash.pop() {
+            self.inner = stash;
+            self.recorded = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Number of recorded restore points.
+    pub fn depth(&self) -> usize {
+        self.stash.len()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        !self.stash.is_empty()
+    }
+}
+
+impl<T> From<T> for ResettableStack<T> {
+    fn from(inner: T) -> Self {
+        ResettableStack::new(inner)
+    }
+}
+
+impl<T> Resettable for ResettableStack<T> {
+    fn reset(self) -> Self {
+        ResettableStack::new(self.reset_inner())
+    }
+}
+
+impl<T> fmt::Debug for ResettableStack<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResettableStack, Resettable};
+
+    #[test]
+    fn single_level() {
+        let mut stack = ResettableStack::new(1);
+        *stack += 1;
+        *stack += 1;
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(stack.reset_inner(), 1);
+    }
+
+    #[test]
+    fn undo() {
+        let mut stack = ResettableStack::new(1);
+        assert!(!stack.undo());
+
+        *stack += 1;
+        stack.checkpoint();
+        *stack += 1;
+        *stack += 1;
+        stack.checkpoint();
+        stack.checkpoint();
+        *stack += 1;
+        assert_eq!(*stack, 5);
+        assert_eq!(stack.depth(), 3);
+
+        assert!(stack.undo());
+        assert_eq!(*stack, 4);
+        assert!(stack.undo());
+        assert_eq!(*stack, 2);
+
+        *stack += 10;
+        assert_eq!(stack.depth(), 2);
+        assert_eq!(stack.clone().reset_inner(), 1);
+
+        let stack = stack.reset();
+        assert!(!stack.is_dirty());
+        assert_eq!(*stack, 1);
+    }
+}
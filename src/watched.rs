@@ -0,0 +1,125 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+use core::{
+    ops::{Deref, DerefMut},
+    fmt,
+};
+
+use super::{Resettable, ResettableWrapper};
+
+/// A `ResettableWrapper` that calls `on_dirty` when it goes from clean to
+/// dirty, i.e. on the first mutable access after creation, a reset or a
+/// commit. Later mutations don't call it again.
+pub struct ResettableWatched<T, F>
+where
+    F: FnMut(),
+{
+    wrapper: ResettableWrapper<T>,
+    on_dirty: F,
+}
+
+impl<T, F> ResettableWatched<T, F>
+where
+    F: FnMut(),
+{
+    pub fn new(inner: T, on_dirty: F) -> Self {
+        ResettableWatched {
+            wrapper: ResettableWrapper::new(inner),
+            on_dirty,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.wrapper.into_inner()
+    }
+
+    pub fn reset_inner(self) -> T {
+        self.wrapper.reset_inner()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.wrapper.is_dirty()
+    }
+
+    pub fn reset_in_place(&mut self) {
+        self.wrapper.reset_in_place()
+    }
+
+    pub fn commit_in_place(&mut self) {
+        self.wrapper.commit_in_place()
+    }
+}
+
+impl<T, F> Deref for ResettableWatched<T, F>
+where
+    F: FnMut(),
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.wrapper
+    }
+}
+
+impl<T, F> DerefMut for ResettableWatched<T, F>
+where
+    T: Clone,
+    F: FnMut(),
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.wrapper.is_clean() {
+            (self.on_dirty)();
+        }
+        &mut self.wrapper
+    }
+}
+
+impl<T, F> Resettable for ResettableWatched<T, F>
+where
+    F: FnMut(),
+{
+    fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+}
+
+impl<T, F> fmt::Debug for ResettableWatched<T, F>
+where
+    T: fmt::Debug,
+    F: FnMut(),
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.wrapper.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::ResettableWatched;
+
+    #[test]
+    fn fires_on_clean_to_dirty() {
+        let calls = Cell::new(0);
+        let mut watched = ResettableWatched::new(vec![1], || calls.set(calls.get() + 1));
+        assert_eq!(*watched, [1]);
+        assert_eq!(calls.get(), 0);
+
+        watched.push(2);
+        watched.push(3);
+        assert_eq!(calls.get(), 1);
+
+        watched.reset_in_place();
+        watched.push(4);
+        assert_eq!(calls.get(), 2);
+
+        watched.commit_in_place();
+        watched.push(5);
+        watched.push(6);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(watched.reset_inner(), [1, 4]);
+    }
+}
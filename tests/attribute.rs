@@ -0,0 +1,110 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "derive")]
+
+use resettable::{resettable, Resettable, ResettableWrapper};
+
+#[resettable]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Form {
+    pub name: String,
+    #[resettable(group = "numbers")]
+    age: u32,
+    #[resettable(skip)]
+    visits: u64,
+}
+
+#[resettable]
+#[derive(Debug, PartialEq)]
+struct Pair<T>(T, #[resettable(skip)] T);
+
+#[test]
+fn wrapped_fields() {
+    let mut form = Form {
+        name: ResettableWrapper::new("foo".to_string()),
+        age: 30.into(),
+        visits: 1,
+    };
+    form.name.push_str("bar");
+    *form.age = 31;
+    form.visits = 2;
+    assert_eq!(form.name(), "foobar");
+    assert_eq!(*form.age(), 31);
+
+    let form = form.reset_group_numbers();
+    assert_eq!(form.name(), "foobar");
+    assert_eq!(*form.age(), 30);
+
+    let form = form.reset();
+    assert_eq!(form.name(), "foo");
+    assert!(form.name.is_clean());
+    assert_eq!(form.visits, 2);
+}
+
+#[test]
+fn tuple_struct() {
+    let mut pair = Pair(ResettableWrapper::new(1), 2);
+    *pair.0 = 3;
+    pair.1 = 4;
+    assert_eq!(pair.reset(), Pair(1.into(), 4));
+}
+
+#[resettable]
+#[resettable(report)]
+struct Address {
+    city: String,
+    zip: u32,
+}
+
+#[resettable(report)]
+struct Customer {
+    name: String,
+    #[resettable(flatten)]
+    address: Address,
+}
+
+#[test]
+fn report() {
+    let mut customer = Customer {
+        name: "foo".to_string().into(),
+        address: Address {
+            city: "bar".to_string().into(),
+            zip: 1.into(),
+        },
+    };
+    assert!(customer.dirty_fields().is_empty());
+
+    customer.name.push('!');
+    *customer.address.zip = 2;
+    assert_eq!(customer.address.dirty_fields(), ["zip"]);
+    assert_eq!(customer.dirty_paths(), ["name", "address.zip"]);
+
+    let customer = customer.reset();
+    assert_eq!(customer.address.city(), "bar");
+    assert!(customer.dirty_paths().is_empty());
+}
+
+fn rewind(timestamp: u64) -> u64 {
+    timestamp.saturating_sub(60)
+}
+
+#[resettable]
+struct Session {
+    user: String,
+    #[resettable(with = "rewind")]
+    timestamp: u64,
+}
+
+#[test]
+fn custom_reset() {
+    let mut session = Session {
+        user: "foo".to_string().into(),
+        timestamp: 100,
+    };
+    session.user.push('!');
+
+    let session = session.reset();
+    assert_eq!(session.user(), "foo");
+    assert_eq!(session.timestamp, 40);
+}
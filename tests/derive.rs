@@ -0,0 +1,533 @@
+// Copyright 2021 Vladislav Melnik
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "derive")]
+
+use std::{
+    marker::PhantomData,
+    num::{NonZeroU32, Wrapping},
+};
+
+use resettable::{Resettable, ResettableMut, ResettableWrapper};
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable)]
+struct Named {
+    first: ResettableWrapper<u32>,
+    second: ResettableWrapper<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable)]
+enum State {
+    Empty,
+    Counter(ResettableWrapper<u32>),
+    Form {
+        name: ResettableWrapper<String>,
+        age: ResettableWrapper<u32>,
+    },
+}
+
+#[test]
+fn named_struct() {
+    let original = Named {
+        first: 1.into(),
+        second: "foo".to_string().into(),
+    };
+    let mut value = original.clone();
+    *value.first += 1;
+    value.second.push_str("bar");
+
+    assert_eq!(value.reset(), original);
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(State::Empty.reset(), State::Empty);
+
+    let mut counter = ResettableWrapper::new(1);
+    *counter += 1;
+    assert_eq!(State::Counter(counter).reset(), State::Counter(1.into()));
+
+    let mut name = ResettableWrapper::new("foo".to_string());
+    name.push_str("bar");
+    let form = State::Form {
+        name,
+        age: 30.into(),
+    };
+    assert_eq!(
+        form.reset(),
+        State::Form {
+            name: "foo".to_string().into(),
+            age: 30.into(),
+        },
+    );
+}
+
+#[derive(Debug, PartialEq, Resettable)]
+struct Generic<'a, T, U>
+where
+    U: Copy,
+{
+    first: ResettableWrapper<T>,
+    second: Option<ResettableWrapper<U>>,
+    label: ResettableWrapper<&'a str>,
+}
+
+#[test]
+fn generic_struct() {
+    let mut value = Generic {
+        first: ResettableWrapper::new(vec![1, 2]),
+        second: Some(ResettableWrapper::new(1.5)),
+        label: ResettableWrapper::new("foo"),
+    };
+    value.first.push(3);
+    *value.label = "bar";
+    if let Some(second) = &mut value.second {
+        **second = 2.5;
+    }
+
+    let value = value.reset();
+    assert_eq!(*value.first, [1, 2]);
+    assert_eq!(value.second.map(ResettableWrapper::into_inner), Some(1.5));
+    assert_eq!(*value.label, "foo");
+}
+
+struct Handle(u32);
+
+#[derive(Resettable)]
+struct WithSkipped<T> {
+    value: ResettableWrapper<u32>,
+    #[resettable(skip)]
+    handle: Handle,
+    #[resettable(skip)]
+    cache: T,
+}
+
+#[test]
+fn skipped_fields() {
+    let mut value = WithSkipped {
+        value: ResettableWrapper::new(1),
+        handle: Handle(7),
+        cache: Vec::<u8>::new(),
+    };
+    *value.value = 2;
+    value.cache.push(1);
+
+    let value = value.reset();
+    assert_eq!(*value.value, 1);
+    assert_eq!(value.handle.0, 7);
+    assert_eq!(value.cache, [1]);
+}
+
+mod clock {
+    pub fn rewind(timestamp: u64) -> u64 {
+        timestamp.saturating_sub(60)
+    }
+}
+
+#[derive(Resettable)]
+struct WithCustom {
+    value: ResettableWrapper<u32>,
+    #[resettable(with = "clock::rewind")]
+    timestamp: u64,
+}
+
+#[test]
+fn custom_reset() {
+    let mut value = WithCustom {
+        value: ResettableWrapper::new(1),
+        timestamp: 100,
+    };
+    *value.value = 2;
+
+    let value = value.reset();
+    assert_eq!(*value.value, 1);
+    assert_eq!(value.timestamp, 40);
+}
+
+#[derive(Debug, Resettable)]
+struct Form {
+    #[resettable(group = "editor")]
+    title: ResettableWrapper<String>,
+    #[resettable(group = "editor", group = "meta")]
+    tags: ResettableWrapper<Vec<String>>,
+    #[resettable(group = "meta")]
+    author: ResettableWrapper<String>,
+    revision: ResettableWrapper<u32>,
+}
+
+#[test]
+fn groups() {
+    let mut form = Form {
+        title: ResettableWrapper::new("draft".to_string()),
+        tags: ResettableWrapper::new(vec![]),
+        author: ResettableWrapper::new("alice".to_string()),
+        revision: ResettableWrapper::new(1),
+    };
+    *form.title = "final".to_string();
+    form.tags.push("news".to_string());
+    *form.author = "bob".to_string();
+    *form.revision = 2;
+
+    let form = form.reset_group_editor();
+    assert_eq!(*form.title, "draft");
+    assert!(form.tags.is_empty());
+    assert_eq!(*form.author, "bob");
+    assert_eq!(*form.revision, 2);
+
+    let form = form.reset_group_meta();
+    assert_eq!(*form.author, "alice");
+    assert_eq!(*form.revision, 2);
+
+    let form = form.reset();
+    assert_eq!(*form.revision, 1);
+}
+
+#[derive(Debug, PartialEq, Eq, ResettableMut)]
+enum Editor<T> {
+    Closed,
+    Open {
+        text: ResettableWrapper<T>,
+        #[resettable(skip)]
+        cursor: usize,
+    },
+    Split(ResettableWrapper<T>, Vec<ResettableWrapper<T>>),
+}
+
+#[test]
+fn reset_mut() {
+    let mut editor = Editor::Open {
+        text: ResettableWrapper::new("foo".to_string()),
+        cursor: 0,
+    };
+    if let Editor::Open { text, cursor } = &mut editor {
+        text.push_str("bar");
+        *cursor = 6;
+    }
+    let borrowed = &mut editor;
+    borrowed.reset_mut();
+    assert_eq!(
+        editor,
+        Editor::Open {
+            text: "foo".to_string().into(),
+            cursor: 6,
+        },
+    );
+
+    let mut editor = Editor::Split(1.into(), vec![2.into()]);
+    if let Editor::Split(first, rest) = &mut editor {
+        **first += 1;
+        *rest[0] = 5;
+    }
+    editor.reset_mut();
+    assert_eq!(editor, Editor::Split(1.into(), vec![2.into()]));
+
+    let mut closed = Editor::<u32>::Closed;
+    closed.reset_mut();
+    assert_eq!(closed, Editor::Closed);
+}
+
+#[derive(Debug, PartialEq, Eq, Resettable, ResettableMut)]
+struct Marked<Unit> {
+    value: ResettableWrapper<u32>,
+    unit: PhantomData<Unit>,
+    empty: (),
+}
+
+#[test]
+fn marker_fields() {
+    let mut marked = Marked::<fn() -> str> {
+        value: 1.into(),
+        unit: PhantomData,
+        empty: (),
+    };
+    *marked.value = 2;
+    marked.reset_mut();
+    assert!(marked.value.is_clean());
+
+    *marked.value = 3;
+    assert_eq!(marked.reset().value, 1);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable, ResettableMut)]
+struct Point(ResettableWrapper<i32>, ResettableWrapper<i32>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable, ResettableMut)]
+struct Unit;
+
+#[test]
+fn struct_kinds() {
+    let mut point = Point(1.into(), 2.into());
+    *point.0 = 3;
+    *point.1 = 4;
+    assert_eq!(point.clone().reset(), Point(1.into(), 2.into()));
+    point.reset_mut();
+    assert_eq!(point, Point(1.into(), 2.into()));
+
+    let mut unit = Unit.reset();
+    unit.reset_mut();
+    assert_eq!(unit, Unit);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable)]
+struct Profile {
+    nickname: Option<ResettableWrapper<String>>,
+    age: Option<ResettableWrapper<u32>>,
+}
+
+#[test]
+fn optional_fields() {
+    let mut profile = Profile {
+        nickname: Some("foo".to_string().into()),
+        age: None,
+    };
+    let clean = profile.clone().reset();
+    assert_eq!(clean, profile);
+
+    if let Some(nickname) = &mut profile.nickname {
+        nickname.push_str("bar");
+    }
+    let profile = profile.reset();
+    assert_eq!(profile.nickname.as_deref().map(|n| n.as_str()), Some("foo"));
+    assert!(profile.nickname.unwrap().is_clean());
+    assert_eq!(profile.age, None);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable, ResettableMut)]
+struct Node {
+    value: ResettableWrapper<i32>,
+    children: Vec<Node>,
+}
+
+#[test]
+fn recursive() {
+    let leaf = |value| Node {
+        value: ResettableWrapper::new(value),
+        children: Vec::new(),
+    };
+    let mut tree = Node {
+        value: 0.into(),
+        children: vec![Node {
+            value: 1.into(),
+            children: vec![leaf(2), leaf(3)],
+        }],
+    };
+    let original = tree.clone();
+
+    *tree.children[0].children[1].value = 30;
+    *tree.value = 10;
+    let reset = tree.clone().reset();
+    assert_eq!(reset, original);
+    assert!(reset.children[0].children[1].value.is_clean());
+
+    tree.reset_mut();
+    assert_eq!(tree, original);
+}
+
+#[derive(Resettable)]
+#[resettable(report)]
+struct Settings {
+    theme: ResettableWrapper<String>,
+    scale: resettable::ResettableWrapper<u32>,
+    #[resettable(skip)]
+    cache: ResettableWrapper<u32>,
+    volume: Option<ResettableWrapper<u32>>,
+}
+
+#[derive(Resettable)]
+#[resettable(report)]
+enum Shape {
+    Circle(ResettableWrapper<u32>),
+    Rect {
+        width: ResettableWrapper<u32>,
+        height: ResettableWrapper<u32>,
+    },
+}
+
+#[test]
+fn dirty_fields() {
+    let mut settings = Settings {
+        theme: "dark".to_string().into(),
+        scale: 1.into(),
+        cache: 0.into(),
+        volume: Some(5.into()),
+    };
+    assert!(settings.dirty_fields().is_empty());
+
+    settings.theme.push('!');
+    *settings.scale = 2;
+    *settings.cache = 1;
+    **settings.volume.as_mut().unwrap() = 6;
+    assert_eq!(settings.dirty_fields(), ["theme", "scale"]);
+    assert!(settings.reset().dirty_fields().is_empty());
+
+    let mut circle = Shape::Circle(1.into());
+    if let Shape::Circle(radius) = &mut circle {
+        **radius = 2;
+    }
+    assert_eq!(circle.dirty_fields(), ["0"]);
+
+    let mut rect = Shape::Rect {
+        width: 1.into(),
+        height: 1.into(),
+    };
+    if let Shape::Rect { height, .. } = &mut rect {
+        **height = 2;
+    }
+    assert_eq!(rect.dirty_fields(), ["height"]);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable, ResettableMut)]
+struct Mixed {
+    wrapped: ResettableWrapper<i32>,
+    plain: i32,
+    label: String,
+}
+
+#[test]
+fn plain_fields() {
+    let mut mixed = Mixed {
+        wrapped: 1.into(),
+        plain: 1,
+        label: "foo".to_string(),
+    };
+    *mixed.wrapped = 2;
+    mixed.plain = 2;
+    mixed.label.push_str("bar");
+
+    let reset = mixed.clone().reset();
+    assert_eq!(reset.wrapped, 1);
+    assert_eq!(reset.plain, 2);
+    assert_eq!(reset.label, "foobar");
+
+    mixed.reset_mut();
+    assert_eq!(mixed, reset);
+}
+
+#[derive(Resettable)]
+#[resettable(report)]
+struct Address {
+    city: ResettableWrapper<String>,
+    zip: ResettableWrapper<u32>,
+}
+
+#[derive(Resettable)]
+#[resettable(report)]
+struct Customer {
+    name: ResettableWrapper<String>,
+    #[resettable(flatten)]
+    address: Address,
+}
+
+#[test]
+fn dirty_paths() {
+    let mut customer = Customer {
+        name: "foo".to_string().into(),
+        address: Address {
+            city: "bar".to_string().into(),
+            zip: 1.into(),
+        },
+    };
+    assert!(customer.dirty_paths().is_empty());
+
+    customer.name.push('!');
+    *customer.address.zip = 2;
+    assert_eq!(customer.dirty_fields(), ["name"]);
+    assert_eq!(customer.dirty_paths(), ["name", "address.zip"]);
+
+    let customer = customer.reset();
+    assert!(customer.dirty_paths().is_empty());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable)]
+struct Counter {
+    hits: Wrapping<u32>,
+    step: NonZeroU32,
+    limit: ResettableWrapper<u32>,
+}
+
+#[test]
+fn numeric_newtypes() {
+    let mut counter = Counter {
+        hits: Wrapping(u32::MAX),
+        step: NonZeroU32::new(2).unwrap(),
+        limit: 10.into(),
+    };
+    counter.hits += Wrapping(2);
+    *counter.limit = 20;
+
+    let counter = counter.reset();
+    assert_eq!(counter.hits, Wrapping(1));
+    assert_eq!(counter.step.get(), 2);
+    assert_eq!(counter.limit, 10);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Resettable, ResettableMut)]
+struct Tree<T> {
+    value: ResettableWrapper<T>,
+    children: Vec<Tree<T>>,
+}
+
+#[test]
+fn generic_recursive() {
+    let mut tree = Tree {
+        value: ResettableWrapper::new("root".to_string()),
+        children: vec![Tree {
+            value: ResettableWrapper::new("leaf".to_string()),
+            children: Vec::new(),
+        }],
+    };
+    let original = tree.clone();
+
+    tree.children[0].value.push('!');
+    let reset = tree.clone().reset();
+    assert_eq!(reset, original);
+    assert!(reset.children[0].value.is_clean());
+
+    tree.reset_mut();
+    assert_eq!(tree, original);
+}
+
+#[derive(Debug, Resettable)]
+struct Swap<A, B> {
+    a: A,
+    next: Option<Box<Swap<B, A>>>,
+}
+
+#[test]
+fn permuted_recursive() {
+    let mut swap = Swap {
+        a: ResettableWrapper::new(1),
+        next: Some(Box::new(Swap {
+            a: ResettableWrapper::new("foo".to_string()),
+            next: None,
+        })),
+    };
+    *swap.a = 2;
+    swap.next.as_mut().unwrap().a.push('!');
+
+    let swap = swap.reset();
+    assert_eq!(*swap.a, 1);
+    assert_eq!(*swap.next.unwrap().a, "foo");
+}
+
+mod shadow {
+    use resettable::Resettable;
+
+    // Not recursive, `super::Tree<T>` is a different type.
+    #[derive(Resettable)]
+    pub struct Tree<T> {
+        pub inner: super::Tree<T>,
+    }
+}
+
+#[test]
+fn same_name() {
+    let mut tree = shadow::Tree {
+        inner: Tree {
+            value: ResettableWrapper::new(1),
+            children: Vec::new(),
+        },
+    };
+    *tree.inner.value = 2;
+    assert_eq!(*tree.reset().inner.value, 1);
+}
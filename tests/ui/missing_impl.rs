@@ -0,0 +1,14 @@
+use resettable::{Resettable, ResettableWrapper};
+
+struct Handle;
+
+#[derive(Resettable)]
+struct Form {
+    value: ResettableWrapper<u32>,
+    handle: Handle,
+}
+
+#[derive(Resettable)]
+struct Pair(ResettableWrapper<u32>, Handle);
+
+fn main() {}
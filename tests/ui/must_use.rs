@@ -0,0 +1,11 @@
+#![deny(unused_must_use)]
+
+use resettable::{Resettable, ResettableWrapper};
+
+fn main() {
+    let mut wrapper = ResettableWrapper::new(1);
+    *wrapper = 2;
+    wrapper.clone().reset();
+    wrapper.clone().commit();
+    wrapper.reset_inner();
+}
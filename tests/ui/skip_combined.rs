@@ -0,0 +1,10 @@
+use resettable::{Resettable, ResettableWrapper};
+
+#[derive(Resettable)]
+struct Form {
+    value: ResettableWrapper<u32>,
+    #[resettable(skip, group = "numbers")]
+    count: u32,
+}
+
+fn main() {}